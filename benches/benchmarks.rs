@@ -1,11 +1,17 @@
 use std::hint::black_box;
 
-use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use criterion::{
+    criterion_group, criterion_main, measurement::WallTime, BenchmarkGroup, BenchmarkId, Criterion,
+    Throughput,
+};
 use rand::{Rng, SeedableRng};
 use rand_chacha::ChaCha8Rng;
 
 use simd_erasure_core::{
-    engine::{DefaultEngine, Engine, Naive, NoSimd, ShardsRefMut, GF_ORDER},
+    engine::{
+        utils, DefaultEngine, Engine, EngineExt, GfElement, Naive, NoSimd, ShardsRefMut,
+        GF_MODULUS, GF_ORDER,
+    },
     rate::{
         HighRateDecoder, HighRateEncoder, LowRateDecoder, LowRateEncoder, RateDecoder, RateEncoder,
     },
@@ -114,7 +120,14 @@ fn benchmarks_main(c: &mut Criterion) {
 
         let max_original_loss_count = std::cmp::min(original_count, recovery_count);
 
-        for loss_percent in [1, 100] {
+        // Intermediate loss percentages change FFT truncation,
+        // so these are measured only for a couple of configurations.
+        let loss_percents: &[usize] = match (original_count, recovery_count) {
+            (1024, 1024) | (8192, 8192) => &[1, 10, 25, 50, 75, 100],
+            _ => &[1, 100],
+        };
+
+        for &loss_percent in loss_percents {
             // We round up to make sure at least one shard is lost for low shard counts.
             let original_loss_count = (max_original_loss_count * loss_percent).div_ceil(100);
             let original_provided_count = original_count - original_loss_count;
@@ -141,8 +154,79 @@ fn benchmarks_main(c: &mut Criterion) {
                 },
             );
         }
+
+        // ReedSolomonDecoder - RECOVERY ONLY
+        //
+        // No original shards and all recovery shards provided.
+        // With `original_count == recovery_count` this is same as 100% loss above.
+
+        if recovery_count > original_count {
+            let mut decoder =
+                ReedSolomonDecoder::new(original_count, recovery_count, SHARD_BYTES).unwrap();
+
+            let id = format!("{}:{} (recovery only)", original_count, recovery_count);
+
+            group.bench_with_input(
+                BenchmarkId::new("ReedSolomonDecoder", &id),
+                &recovery,
+                |b, recovery| {
+                    b.iter(|| {
+                        for (index, recovery) in recovery.iter().enumerate() {
+                            decoder.add_recovery_shard(index, recovery).unwrap();
+                        }
+                        decoder.decode().unwrap();
+                    });
+                },
+            );
+        }
     }
 
+    // multi_encode - TIERED RECOVERY COUNTS
+    //
+    // All these use low rate, so `multi_encode` encodes only once.
+
+    let original_count = 1024;
+    let recovery_counts = [2048, 4096, 8192];
+    let original = generate_shards(original_count, SHARD_BYTES, 0);
+
+    group.sample_size(10);
+    group.throughput(Throughput::Bytes(
+        ((original_count + recovery_counts.iter().sum::<usize>()) * SHARD_BYTES) as u64,
+    ));
+
+    let id = format!("{}:{:?}", original_count, recovery_counts);
+
+    group.bench_with_input(
+        BenchmarkId::new("multi_encode", &id),
+        &original,
+        |b, original| {
+            b.iter(|| {
+                simd_erasure_core::multi_encode(
+                    original_count,
+                    &recovery_counts,
+                    SHARD_BYTES,
+                    original,
+                )
+                .unwrap()
+            });
+        },
+    );
+
+    group.bench_with_input(
+        BenchmarkId::new("encode per recovery count", &id),
+        &original,
+        |b, original| {
+            b.iter(|| {
+                recovery_counts
+                    .iter()
+                    .map(|&recovery_count| {
+                        simd_erasure_core::encode(original_count, recovery_count, original).unwrap()
+                    })
+                    .collect::<Vec<_>>()
+            });
+        },
+    );
+
     group.finish();
 }
 
@@ -297,6 +381,171 @@ fn benchmarks_rate_one<E: Engine>(c: &mut Criterion, name: &str, new_engine: fn(
     group.finish();
 }
 
+// ======================================================================
+// BENCHMARKS - SPARSE
+
+// Wraps an engine so that `is_zero_shard` always returns `false`,
+// i.e. encoders/decoders never skip all-zero shards.
+#[derive(Debug)]
+struct NoZeroSkip<E>(E);
+
+impl<E: Engine> Engine for NoZeroSkip<E> {
+    fn fft(
+        &self,
+        data: &mut ShardsRefMut,
+        pos: usize,
+        size: usize,
+        truncated_size: usize,
+        skew_delta: usize,
+    ) {
+        self.0.fft(data, pos, size, truncated_size, skew_delta)
+    }
+
+    fn ifft(
+        &self,
+        data: &mut ShardsRefMut,
+        pos: usize,
+        size: usize,
+        truncated_size: usize,
+        skew_delta: usize,
+    ) {
+        self.0.ifft(data, pos, size, truncated_size, skew_delta)
+    }
+
+    fn mul(&self, x: &mut [[u8; 64]], log_m: GfElement) {
+        self.0.mul(x, log_m)
+    }
+
+    fn add_xor(&self, dst: &mut [[u8; 64]], src: &[[u8; 64]]) {
+        self.0.add_xor(dst, src)
+    }
+
+    fn eval_poly(erasures: &mut [GfElement; GF_ORDER], truncated_size: usize) {
+        E::eval_poly(erasures, truncated_size)
+    }
+
+    fn eval_poly_in_field(&self, erasures: &mut [GfElement; GF_ORDER], truncated_size: usize) {
+        self.0.eval_poly_in_field(erasures, truncated_size)
+    }
+
+    fn is_zero_shard(&self, _x: &[[u8; 64]]) -> bool {
+        false
+    }
+
+    fn xor_then_mul(&self, x: &mut [[u8; 64]], y: &[[u8; 64]], log_m: GfElement) {
+        self.0.xor_then_mul(x, y, log_m)
+    }
+}
+
+fn benchmarks_sparse_one<E: Engine, F: Fn() -> E>(
+    group: &mut BenchmarkGroup<'_, WallTime>,
+    name: &str,
+    original_count: usize,
+    recovery_count: usize,
+    original: &[Vec<u8>],
+    new_engine: F,
+) {
+    let recovery = simd_erasure_core::encode(original_count, recovery_count, original).unwrap();
+
+    // HighRateEncoder
+
+    let mut encoder = HighRateEncoder::new(
+        original_count,
+        recovery_count,
+        SHARD_BYTES,
+        new_engine(),
+        None,
+    )
+    .unwrap();
+
+    group.bench_with_input(
+        BenchmarkId::new("HighRateEncoder", name),
+        original,
+        |b, original| {
+            b.iter(|| {
+                for original in original {
+                    encoder.add_original_shard(original).unwrap();
+                }
+                encoder.encode().unwrap();
+            });
+        },
+    );
+
+    // HighRateDecoder
+
+    let mut decoder = HighRateDecoder::new(
+        original_count,
+        recovery_count,
+        SHARD_BYTES,
+        new_engine(),
+        None,
+    )
+    .unwrap();
+
+    group.bench_with_input(
+        BenchmarkId::new("HighRateDecoder", name),
+        &recovery,
+        |b, recovery| {
+            b.iter(|| {
+                for index in recovery_count..original_count {
+                    decoder.add_original_shard(index, &original[index]).unwrap();
+                }
+                for (index, recovery) in recovery.iter().enumerate() {
+                    decoder.add_recovery_shard(index, recovery).unwrap();
+                }
+                decoder.decode().unwrap();
+            });
+        },
+    );
+}
+
+// Encodes/decodes with all original shards non-zero ("dense")
+// and with all but every 16th original shard all-zero ("sparse"),
+// so the difference shows the effect of skipping all-zero shards.
+// Sparse input is also run with skipping disabled ("sparse-no-skip")
+// to show the cost of the checks themselves.
+fn benchmarks_sparse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("sparse");
+    group.sample_size(10);
+
+    let original_count = 8192;
+    let recovery_count = 1024;
+
+    group.throughput(Throughput::Bytes(
+        ((original_count + recovery_count) * SHARD_BYTES) as u64,
+    ));
+
+    let dense = generate_shards(original_count, SHARD_BYTES, 0);
+    let mut sparse = dense.clone();
+    for (index, shard) in sparse.iter_mut().enumerate() {
+        if index % 16 != 0 {
+            shard.fill(0);
+        }
+    }
+
+    for (name, original) in [("dense", &dense), ("sparse", &sparse)] {
+        benchmarks_sparse_one(
+            &mut group,
+            name,
+            original_count,
+            recovery_count,
+            original,
+            DefaultEngine::new,
+        );
+    }
+
+    benchmarks_sparse_one(
+        &mut group,
+        "sparse-no-skip",
+        original_count,
+        recovery_count,
+        &sparse,
+        || NoZeroSkip(DefaultEngine::new()),
+    );
+
+    group.finish();
+}
+
 // ======================================================================
 // BENCHMARKS - ENGINES
 
@@ -347,35 +596,168 @@ fn benchmarks_engine_one<E: Engine>(c: &mut Criterion, name: &str, engine: E) {
         b.iter(|| engine.mul(black_box(&mut x), black_box(12345)))
     });
 
-    // FFT IFFT
+    // Multiplying by one, as `0` and `GF_MODULUS`, and by random multiplier.
+    // `mul` has no special cases so these should all take the same time.
 
-    let shards_128_data = &mut generate_shards_64(1, 128 * shard_len_64, 0)[0];
-    let mut shards_128 = ShardsRefMut::new(128, shard_len_64, shards_128_data.as_mut());
+    group.bench_function("mul log_m 0", |b| {
+        b.iter(|| engine.mul(black_box(&mut x), black_box(0)))
+    });
 
-    group.bench_function("FFT 128", |b| {
+    group.bench_function("mul log_m GF_MODULUS", |b| {
+        b.iter(|| engine.mul(black_box(&mut x), black_box(GF_MODULUS)))
+    });
+
+    let log_m_random: GfElement = rng.random();
+
+    group.bench_function("mul log_m random", |b| {
+        b.iter(|| engine.mul(black_box(&mut x), black_box(log_m_random)))
+    });
+
+    // Multiplying by zero.
+    //
+    // `mul` takes logarithm of a non-zero multiplier so zero can't be given
+    // to it. Result of multiplying by zero is all-zero shard, so this times
+    // what an early-out for zero multiplier would do, to compare against
+    // "mul log_m random" above.
+
+    group.bench_function("mul by zero", |b| {
+        b.iter(|| black_box(&mut x).fill([0; 64]))
+    });
+
+    // IS_ZERO_SHARD
+    //
+    // Checking an all-zero shard vs. multiplying it,
+    // i.e. the work saved per skipped multiplication.
+
+    let mut zero = vec![[0u8; 64]; shard_len_64];
+
+    group.bench_function("is_zero_shard", |b| {
+        b.iter(|| engine.is_zero_shard(black_box(&zero)))
+    });
+
+    group.bench_function("mul zero shard", |b| {
+        b.iter(|| engine.mul(black_box(&mut zero), black_box(12345)))
+    });
+
+    // MUL 1024 SHARDS
+
+    let mut shards_1024 = generate_shards_64(1, 1024 * shard_len_64, 0).remove(0);
+
+    group.bench_function("mul 1024", |b| {
+        b.iter(|| engine.mul(black_box(&mut shards_1024), black_box(12345)))
+    });
+
+    // MUL 256 SHARDS - DISTINCT MULTIPLIERS
+    //
+    // One `mul` per shard, each with its own multiplier,
+    // vs. one `mul` over all shards with single multiplier.
+
+    let mut shards_256 = generate_shards_64(256, shard_len_64, 0);
+    let log_ms: Vec<GfElement> = (0..256).map(|i| 12345 + 97 * i).collect();
+
+    group.bench_function("mul 256 distinct", |b| {
         b.iter(|| {
-            engine.fft(
-                black_box(&mut shards_128),
-                black_box(0),
-                black_box(128),
-                black_box(128),
-                black_box(128),
-            )
+            for (shard, &log_m) in shards_256.iter_mut().zip(&log_ms) {
+                engine.mul(black_box(shard), black_box(log_m));
+            }
         })
     });
 
-    group.bench_function("IFFT 128", |b| {
+    let mut shards_256_flat = shards_256.concat();
+
+    group.bench_function("mul 256 same", |b| {
+        b.iter(|| engine.mul(black_box(&mut shards_256_flat), black_box(12345)))
+    });
+
+    // XOR_THEN_MUL
+
+    let y = &generate_shards_64(1, shard_len_64, 1)[0];
+    let mut tmp = y.clone();
+
+    group.bench_function("mul + xor", |b| {
         b.iter(|| {
-            engine.ifft(
-                black_box(&mut shards_128),
-                black_box(0),
-                black_box(128),
-                black_box(128),
-                black_box(128),
-            )
+            tmp.copy_from_slice(y);
+            engine.mul(black_box(&mut tmp), black_box(12345));
+            utils::xor(black_box(&mut x), &tmp);
         })
     });
 
+    group.bench_function("xor_then_mul", |b| {
+        b.iter(|| engine.xor_then_mul(black_box(&mut x), black_box(y), black_box(12345)))
+    });
+
+    // FFT IFFT
+
+    let shards_128_data = &mut generate_shards_64(1, 128 * shard_len_64, 0)[0];
+    let mut shards_128 = ShardsRefMut::from_vec_mut(128, shard_len_64, shards_128_data);
+
+    group.bench_function("FFT 128", |b| {
+        b.iter(|| engine.fft_simple(black_box(&mut shards_128), black_box(128)))
+    });
+
+    group.bench_function("IFFT 128", |b| {
+        b.iter(|| engine.ifft_simple(black_box(&mut shards_128), black_box(128)))
+    });
+
+    // FFT IFFT - 64 BYTE SHARDS
+
+    let shards_128_64b_data = &mut generate_shards_64(1, 128, 0)[0];
+    let mut shards_128_64b = ShardsRefMut::from_vec_mut(128, 1, shards_128_64b_data);
+
+    group.bench_function("FFT 128 x 64 bytes", |b| {
+        b.iter(|| engine.fft_simple(black_box(&mut shards_128_64b), black_box(128)))
+    });
+
+    group.finish();
+}
+
+// ======================================================================
+// BENCHMARKS - SHARDS OPS
+
+// Measures split-borrow overhead of `ShardsRefMut::dist2_mut` and
+// `ShardsRefMut::dist4_mut` which are called once per butterfly.
+// Each iteration visits every butterfly of one FFT layer of 256 shards
+// without doing any arithmetic, so this is pure call overhead.
+//
+// `copy_within` and `flat2_mut` are crate-private and are
+// measured only indirectly as part of `benches_rate`.
+fn benchmarks_shards_ops(c: &mut Criterion) {
+    const SHARD_COUNT: usize = 256;
+
+    let mut group = c.benchmark_group("shards-ops");
+    let shard_len_64 = SHARD_BYTES / 64;
+
+    let data = &mut generate_shards_64(1, SHARD_COUNT * shard_len_64, 0)[0];
+    let mut shards = ShardsRefMut::from_vec_mut(SHARD_COUNT, shard_len_64, data);
+
+    // DIST2_MUT
+
+    for dist in [1, 8, 64] {
+        group.bench_with_input(BenchmarkId::new("dist2_mut", dist), &dist, |b, &dist| {
+            b.iter(|| {
+                for r in (0..SHARD_COUNT).step_by(dist * 2) {
+                    for i in r..r + dist {
+                        black_box(shards.dist2_mut(black_box(i), black_box(dist)));
+                    }
+                }
+            })
+        });
+    }
+
+    // DIST4_MUT
+
+    for dist in [1, 8, 32] {
+        group.bench_with_input(BenchmarkId::new("dist4_mut", dist), &dist, |b, &dist| {
+            b.iter(|| {
+                for r in (0..SHARD_COUNT).step_by(dist * 4) {
+                    for i in r..r + dist {
+                        black_box(shards.dist4_mut(black_box(i), black_box(dist)));
+                    }
+                }
+            })
+        });
+    }
+
     group.finish();
 }
 
@@ -384,5 +766,13 @@ fn benchmarks_engine_one<E: Engine>(c: &mut Criterion, name: &str, engine: E) {
 
 criterion_group!(benches_main, benchmarks_main);
 criterion_group!(benches_rate, benchmarks_rate);
+criterion_group!(benches_sparse, benchmarks_sparse);
 criterion_group!(benches_engine, benchmarks_engine);
-criterion_main!(benches_main, benches_rate, benches_engine);
+criterion_group!(benches_shards_ops, benchmarks_shards_ops);
+criterion_main!(
+    benches_main,
+    benches_rate,
+    benches_sparse,
+    benches_engine,
+    benches_shards_ops
+);
@@ -0,0 +1,157 @@
+//! Simple GF(2^16) checksums of shard data.
+//!
+//! Shard bytes are read as little-endian 16-bit GF(2^16) elements,
+//! with odd last byte, if any, being the low byte of last element.
+//!
+//! These checksums are linear, i.e. checksum of XOR of two shard sets
+//! is XOR of their checksums. So any change within a single element
+//! is detected, but changes in several elements may cancel each other.
+//! They are meant for fast detection of accidental corruption,
+//! not as protection against deliberate modification.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use simd_erasure_core::checksum;
+//!
+//! let mut shards = [[1u8, 2], [3, 4], [5, 6]];
+//! let weights = [1, 2, 3];
+//!
+//! let plain = checksum::gf_checksum(&shards);
+//! let weighted = checksum::gf_checksum_weighted(&shards, &weights);
+//!
+//! // Swapping shards isn't detected without weights ...
+//! shards.swap(0, 1);
+//! assert_eq!(checksum::gf_checksum(&shards), plain);
+//! // ... but is with distinct weights.
+//! assert_ne!(checksum::gf_checksum_weighted(&shards, &weights), weighted);
+//! ```
+
+use crate::engine::{tables, GfElement};
+
+// ======================================================================
+// FUNCTIONS - PUBLIC
+
+/// Returns XOR of all GF(2^16) elements of all `shards`,
+/// i.e. their sum in GF(2^16).
+///
+/// See [module documentation](self) for details.
+pub fn gf_checksum<T: AsRef<[u8]>>(shards: &[T]) -> u16 {
+    shards
+        .iter()
+        .fold(0, |checksum, shard| checksum ^ shard_sum(shard.as_ref()))
+}
+
+/// Returns sum in GF(2^16) of sums of elements of each shard
+/// multiplied by weight of that shard.
+///
+/// With distinct non-zero weights this also detects shards
+/// being swapped, which [`gf_checksum`] doesn't.
+/// Shard with weight `0` is ignored.
+///
+/// See [module documentation](self) for details.
+///
+/// # Panics
+///
+/// If `weights` and `shards` have different lengths.
+pub fn gf_checksum_weighted<T: AsRef<[u8]>>(shards: &[T], weights: &[u16]) -> u16 {
+    assert_eq!(shards.len(), weights.len());
+
+    let exp_log = tables::get_exp_log();
+
+    shards
+        .iter()
+        .zip(weights)
+        .fold(0, |checksum, (shard, &weight)| {
+            let sum = shard_sum(shard.as_ref());
+            if weight == 0 {
+                checksum
+            } else {
+                checksum
+                    ^ tables::mul(
+                        sum,
+                        exp_log.log[usize::from(weight)],
+                        &exp_log.exp,
+                        &exp_log.log,
+                    )
+            }
+        })
+}
+
+// ======================================================================
+// FUNCTIONS - PRIVATE
+
+fn shard_sum(shard: &[u8]) -> GfElement {
+    let mut chunks = shard.chunks_exact(2);
+    let mut sum = chunks
+        .by_ref()
+        .fold(0, |sum, pair| sum ^ u16::from_le_bytes([pair[0], pair[1]]));
+
+    if let [last] = chunks.remainder() {
+        sum ^= GfElement::from(*last);
+    }
+
+    sum
+}
+
+// ======================================================================
+// TESTS
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util;
+
+    #[test]
+    fn gf_checksum_detects_any_byte_change() {
+        let mut shards = test_util::generate_original(3, 66, 0);
+        let weights = [1, 2, 0x8000];
+
+        let plain = gf_checksum(&shards);
+        let weighted = gf_checksum_weighted(&shards, &weights);
+
+        for index in 0..3 {
+            for byte in 0..66 {
+                shards[index][byte] ^= 0x5a;
+                assert_ne!(gf_checksum(&shards), plain);
+                assert_ne!(gf_checksum_weighted(&shards, &weights), weighted);
+                shards[index][byte] ^= 0x5a;
+            }
+        }
+
+        assert_eq!(gf_checksum(&shards), plain);
+        assert_eq!(gf_checksum_weighted(&shards, &weights), weighted);
+    }
+
+    #[test]
+    fn gf_checksum_values() {
+        assert_eq!(gf_checksum::<&[u8]>(&[]), 0);
+        assert_eq!(gf_checksum(&[[0x01u8, 0x02, 0x10, 0x20]]), 0x2211);
+        assert_eq!(gf_checksum(&[[0x01u8, 0x02], [0x10, 0x20]]), 0x2211);
+        // Odd last byte is low byte of last element.
+        assert_eq!(gf_checksum(&[&[0x01u8, 0x02, 0x10][..]]), 0x0211);
+    }
+
+    #[test]
+    fn gf_checksum_weighted_values() {
+        let shards = [[0x01u8, 0x02, 0x10, 0x20], [0x34, 0x12, 0, 0]];
+
+        // Weight 1 is plain checksum, weight 0 ignores shard.
+        assert_eq!(gf_checksum_weighted(&shards, &[1, 1]), gf_checksum(&shards));
+        assert_eq!(gf_checksum_weighted(&shards, &[0, 1]), 0x1234);
+        assert_eq!(gf_checksum_weighted(&shards, &[0, 0]), 0);
+
+        let exp_log = tables::get_exp_log();
+        let log_7 = exp_log.log[7];
+        assert_eq!(
+            gf_checksum_weighted(&shards, &[7, 0]),
+            tables::mul(0x2211, log_7, &exp_log.exp, &exp_log.log)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "assertion `left == right` failed")]
+    fn gf_checksum_weighted_different_lengths() {
+        gf_checksum_weighted(&[[0u8; 2]; 2], &[1]);
+    }
+}
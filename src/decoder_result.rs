@@ -1,4 +1,7 @@
-use crate::rate::DecoderWork;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use crate::rate::{DecoderWork, DecodingTimings};
 
 // ======================================================================
 // DecoderResult - PUBLIC
@@ -8,6 +11,11 @@ use crate::rate::DecoderWork;
 /// This struct is created by [`ReedSolomonDecoder::decode`]
 /// and [`RateDecoder::decode`].
 ///
+/// It's only returned when decoding succeeds, so all original shards
+/// are then available: Every index in `0..original_count` was either
+/// added to the decoder or has a restored shard in this result.
+/// There is no need to check this separately.
+///
 /// [`RateDecoder::decode`]: crate::rate::RateDecoder::decode
 /// [`ReedSolomonDecoder::decode`]: crate::ReedSolomonDecoder::decode
 pub struct DecoderResult<'a> {
@@ -15,6 +23,24 @@ pub struct DecoderResult<'a> {
 }
 
 impl DecoderResult<'_> {
+    /// Returns time spent in each step of this decoding.
+    ///
+    /// Returns `None` if `timing` feature isn't enabled
+    /// or if no original shards were missing.
+    ///
+    /// See [`DecoderWork::decode_timings`] for details.
+    pub fn decode_timings(&self) -> Option<DecodingTimings> {
+        self.work.decode_timings()
+    }
+
+    /// Returns number of restored original shards,
+    /// i.e. number of original shards that were missing.
+    ///
+    /// This is same as `restored_original_iter().len()`.
+    pub fn restored_count(&self) -> usize {
+        self.work.missing_original_count()
+    }
+
     /// Returns restored original shard with given `index`
     /// or `None` if given `index` doesn't correspond to
     /// a missing original shard.
@@ -24,9 +50,68 @@ impl DecoderResult<'_> {
 
     /// Returns iterator over all restored original shards
     /// and their indexes, ordered by indexes.
+    ///
+    /// Shards are returned as slices of the working space of the decoder,
+    /// so nothing is copied and each shard can be processed,
+    /// e.g. written to disk, as it's returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simd_erasure_core::ReedSolomonDecoder;
+    ///
+    /// let original = [[1u8; 64], [2u8; 64]];
+    /// let recovery = simd_erasure_core::encode(2, 1, &original)?;
+    ///
+    /// let mut decoder = ReedSolomonDecoder::new(2, 1, 64)?;
+    /// decoder.add_original_shard(1, original[1])?;
+    /// decoder.add_recovery_shard(0, &recovery[0])?;
+    ///
+    /// for (index, shard) in decoder.decode()?.restored_original_iter() {
+    ///     assert_eq!(shard, original[index]);
+    /// }
+    /// # Ok::<(), simd_erasure_core::Error>(())
+    /// ```
     pub fn restored_original_iter(&self) -> RestoredOriginal<'_> {
         RestoredOriginal::new(self.work)
     }
+
+    /// Returns restored original shard with given `index` as an owned `Vec`
+    /// or `None` if given `index` doesn't correspond to
+    /// a missing original shard.
+    ///
+    /// This allows e.g. sending shards to other threads.
+    /// The shard is zeroed in working space and no longer counts as restored,
+    /// so afterwards [`restored_original(index)`] returns `None`,
+    /// [`restored_original_iter`] skips it
+    /// and [`restored_count`] is one less.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simd_erasure_core::ReedSolomonDecoder;
+    ///
+    /// let original = [[1u8; 64], [2u8; 64]];
+    /// let recovery = simd_erasure_core::encode(2, 1, &original)?;
+    ///
+    /// let mut decoder = ReedSolomonDecoder::new(2, 1, 64)?;
+    /// decoder.add_original_shard(1, original[1])?;
+    /// decoder.add_recovery_shard(0, &recovery[0])?;
+    ///
+    /// let mut result = decoder.decode()?;
+    /// let restored: Vec<u8> = result.take_restored_original(0).unwrap();
+    ///
+    /// assert_eq!(restored, original[0]);
+    /// assert_eq!(result.restored_original(0), None);
+    /// # Ok::<(), simd_erasure_core::Error>(())
+    /// ```
+    ///
+    /// [`restored_original(index)`]: DecoderResult::restored_original
+    /// [`restored_original_iter`]: DecoderResult::restored_original_iter
+    /// [`restored_count`]: DecoderResult::restored_count
+    pub fn take_restored_original(&mut self, index: usize) -> Option<Vec<u8>> {
+        self.work.take_restored_original(index)
+    }
 }
 
 // ======================================================================
@@ -87,6 +172,28 @@ impl<'a> Iterator for RestoredOriginal<'a> {
         None
     }
 
+    // Skips `n` restored originals without returning them,
+    // received originals in between are skipped as in `next`.
+    fn nth(&mut self, n: usize) -> Option<(usize, &'a [u8])> {
+        if n >= self.remaining {
+            self.remaining = 0;
+            return None;
+        }
+
+        let mut skip = n;
+        let mut index = self.next_index;
+        while skip > 0 {
+            if self.work.restored_original(index).is_some() {
+                skip -= 1;
+            }
+            index += 1;
+        }
+
+        self.next_index = index;
+        self.remaining -= n;
+        self.next()
+    }
+
     fn size_hint(&self) -> (usize, Option<usize>) {
         (self.remaining, Some(self.remaining))
     }
@@ -169,6 +276,81 @@ mod tests {
         }
     }
 
+    #[test]
+    fn restored_original_nth() {
+        let shard_size = 64;
+        let original = test_util::generate_original(6, shard_size, 0);
+
+        let mut encoder = ReedSolomonEncoder::new(6, 4, shard_size).unwrap();
+        let mut decoder = ReedSolomonDecoder::new(6, 4, shard_size).unwrap();
+
+        for original in &original {
+            encoder.add_original_shard(original).unwrap();
+        }
+
+        let result = encoder.encode().unwrap();
+        let recovery: Vec<_> = result.recovery_iter().collect();
+
+        // Originals 0, 2, 3 and 5 are restored.
+        decoder.add_original_shard(1, &original[1]).unwrap();
+        decoder.add_original_shard(4, &original[4]).unwrap();
+        for (index, recovery) in recovery.iter().enumerate() {
+            decoder.add_recovery_shard(index, recovery).unwrap();
+        }
+
+        let result: DecoderResult = decoder.decode().unwrap();
+        assert_eq!(result.restored_count(), 4);
+
+        let mut iter = result.restored_original_iter();
+        iter.next();
+        iter.next();
+        let third = iter.next();
+
+        let mut iter = result.restored_original_iter();
+        assert_eq!(iter.nth(2), third);
+        assert_eq!(third, Some((3, original[3].as_slice())));
+        assert_eq!(iter.len(), 1);
+        assert_eq!(iter.next(), Some((5, original[5].as_slice())));
+        assert_eq!(iter.next(), None);
+
+        let mut iter = result.restored_original_iter();
+        assert_eq!(iter.nth(1), Some((2, original[2].as_slice())));
+        assert_eq!(iter.nth(2), None);
+        assert_eq!(iter.len(), 0);
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn take_restored_original() {
+        let shard_size = 100;
+        let original = test_util::generate_original(4, shard_size, 0);
+        let recovery = crate::encode(4, 3, &original).unwrap();
+
+        // Originals 0, 2 and 3 are restored.
+        let mut decoder = ReedSolomonDecoder::new(4, 3, shard_size).unwrap();
+        decoder.add_original_shard(1, &original[1]).unwrap();
+        for (index, recovery) in recovery.iter().enumerate() {
+            decoder.add_recovery_shard(index, recovery).unwrap();
+        }
+
+        let mut result: DecoderResult = decoder.decode().unwrap();
+
+        assert_eq!(result.take_restored_original(2), Some(original[2].clone()));
+        assert_eq!(result.restored_original(2), None);
+        assert_eq!(result.take_restored_original(2), None);
+
+        // Received and out-of-range originals can't be taken.
+        assert_eq!(result.take_restored_original(1), None);
+        assert_eq!(result.take_restored_original(4), None);
+
+        assert_eq!(result.restored_count(), 2);
+        let mut iter = result.restored_original_iter();
+        assert_eq!(iter.len(), 2);
+        assert_eq!(iter.next(), Some((0, original[0].as_slice())));
+        assert_eq!(iter.next(), Some((3, original[3].as_slice())));
+        assert_eq!(iter.next(), None);
+    }
+
     #[test]
     fn decoder_result_size_hint() {
         let shard_size = 64;
@@ -192,6 +374,7 @@ mod tests {
 
         let mut iter: RestoredOriginal = result.restored_original_iter();
 
+        assert_eq!(result.restored_count(), 2);
         assert_eq!(iter.len(), 2);
 
         assert!(iter.next().is_some());
@@ -228,6 +411,7 @@ mod tests {
 
         let mut iter: RestoredOriginal = result.restored_original_iter();
 
+        assert_eq!(result.restored_count(), 0);
         assert_eq!(iter.len(), 0);
 
         assert!(iter.next().is_none());
@@ -1,3 +1,6 @@
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
 use crate::rate::EncoderWork;
 
 // ======================================================================
@@ -31,6 +34,33 @@ impl EncoderResult<'_> {
     pub fn recovery_iter(&self) -> Recovery<'_> {
         Recovery::new(self.work)
     }
+
+    /// Returns recovery shard with given `index` as an owned `Vec`
+    /// or `None` if `index >= recovery_count`.
+    ///
+    /// This copies the shard like `recovery(index).map(<[u8]>::to_vec)`
+    /// and then zeroes it in working space, so afterwards
+    /// [`recovery(index)`](EncoderResult::recovery) returns all-zero shard.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simd_erasure_core::ReedSolomonEncoder;
+    ///
+    /// let mut encoder = ReedSolomonEncoder::new(2, 1, 64)?;
+    /// encoder.add_original_shard([1; 64])?;
+    /// encoder.add_original_shard([2; 64])?;
+    ///
+    /// let mut result = encoder.encode()?;
+    /// let recovery: Vec<u8> = result.take_recovery(0).unwrap();
+    ///
+    /// assert_eq!(recovery.len(), 64);
+    /// assert_eq!(result.recovery(0), Some(&[0; 64][..]));
+    /// # Ok::<(), simd_erasure_core::Error>(())
+    /// ```
+    pub fn take_recovery(&mut self, index: usize) -> Option<Vec<u8>> {
+        self.work.take_recovery_shard(index)
+    }
 }
 
 // ======================================================================
@@ -40,6 +70,10 @@ impl<'a> EncoderResult<'a> {
     pub(crate) fn new(work: &'a mut EncoderWork) -> Self {
         Self { work }
     }
+
+    pub(crate) fn zero_input(&mut self) {
+        self.work.zero_input();
+    }
 }
 
 // ======================================================================
@@ -80,6 +114,17 @@ impl<'a> Iterator for Recovery<'a> {
         }
     }
 
+    // `EncoderWork::recovery` is `O(1)` so skipped shards can be jumped over.
+    fn nth(&mut self, n: usize) -> Option<&'a [u8]> {
+        if !self.ended {
+            self.next_index = self
+                .next_index
+                .saturating_add(n)
+                .min(self.work.recovery_count());
+        }
+        self.next()
+    }
+
     fn size_hint(&self) -> (usize, Option<usize>) {
         let remaining = self.work.recovery_count() - self.next_index;
         (remaining, Some(remaining))
@@ -113,7 +158,7 @@ mod tests {
     use crate::{test_util, ReedSolomonEncoder};
 
     #[cfg(not(feature = "std"))]
-    use alloc::vec::Vec;
+    use alloc::vec;
 
     #[test]
     // EncoderResult::recovery
@@ -145,6 +190,57 @@ mod tests {
         test_util::assert_hash(all, test_util::LOW_2_3);
     }
 
+    #[test]
+    fn take_recovery() {
+        let original = test_util::generate_original(2, 1000, 123);
+        let mut encoder = ReedSolomonEncoder::new(2, 3, 1000).unwrap();
+
+        for original in &original {
+            encoder.add_original_shard(original).unwrap();
+        }
+
+        let mut result: EncoderResult = encoder.encode().unwrap();
+
+        let expected = result.recovery(1).unwrap().to_vec();
+        let recovery_0 = result.recovery(0).unwrap().to_vec();
+
+        assert_eq!(result.take_recovery(1), Some(expected));
+        assert_eq!(result.recovery(1), Some(&[0; 1000][..]));
+        assert_eq!(result.take_recovery(1), Some(vec![0; 1000]));
+
+        assert_eq!(result.recovery(0), Some(&recovery_0[..]));
+        assert_eq!(result.take_recovery(3), None);
+    }
+
+    #[test]
+    fn recovery_nth() {
+        let original = test_util::generate_original(2, 1024, 123);
+        let mut encoder = ReedSolomonEncoder::new(2, 5, 1024).unwrap();
+
+        for original in &original {
+            encoder.add_original_shard(original).unwrap();
+        }
+
+        let result: EncoderResult = encoder.encode().unwrap();
+
+        let mut iter = result.recovery_iter();
+        iter.next();
+        iter.next();
+        let third = iter.next();
+
+        let mut iter = result.recovery_iter();
+        assert_eq!(iter.nth(2), third);
+        assert_eq!(iter.nth(2), None);
+        assert_eq!(iter.len(), 0);
+        assert_eq!(iter.next(), None);
+
+        let mut iter = result.recovery_iter();
+        assert_eq!(iter.next(), result.recovery(0));
+        assert_eq!(iter.nth(3), result.recovery(4));
+        assert_eq!(iter.nth(usize::MAX), None);
+        assert_eq!(iter.len(), 0);
+    }
+
     #[test]
     fn encoder_result_size_hint() {
         let original = test_util::generate_original(2, 1024, 123);
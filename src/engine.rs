@@ -26,17 +26,29 @@
 //!     - Default engine which is used when no specific engine is given.
 //!     - Automatically selects best engine at runtime.
 //!
+//! All engines are `Send` and `Sync`: They only hold `&'static` references
+//! to lookup [`tables`] which are never modified after initialization,
+//! so a single engine can be shared between threads, e.g. with `Arc`.
+//!
 //! [simple usage]: crate#simple-usage
 //! [basic usage]: crate#basic-usage
 //! [`ReedSolomonEncoder`]: crate::ReedSolomonEncoder
 //! [`ReedSolomonDecoder`]: crate::ReedSolomonDecoder
 //! [`rate`]: crate::rate
 
+use core::fmt;
+
 pub(crate) use self::shards::Shards;
-pub(crate) use utils::{fft_skew_end, formal_derivative, ifft_skew_end, xor_within};
+pub(crate) use utils::{
+    butterfly_count, fft_skew_end, formal_derivative, ifft_skew_end, xor_within,
+};
 
 pub use self::{
-    engine_default::DefaultEngine, engine_naive::Naive, engine_nosimd::NoSimd, shards::ShardsRefMut,
+    engine_default::DefaultEngine,
+    engine_naive::Naive,
+    engine_nosimd::NoSimd,
+    field::{GfField, GfPolynomial},
+    shards::{ShardsChunks, ShardsRefMut, ShardsView},
 };
 
 #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
@@ -57,6 +69,7 @@ mod engine_ssse3;
 #[cfg(target_arch = "aarch64")]
 mod engine_neon;
 
+mod field;
 mod fwht;
 mod shards;
 
@@ -101,7 +114,36 @@ pub type GfElement = u16;
 ///
 /// [`Naive`] engine is provided for those who want to
 /// study the source code to understand [`Engine`].
-pub trait Engine {
+///
+/// `Sync` is not required, but an engine must be `Send + Sync`
+/// to be usable with [`DefaultEngine`] or to be shared between threads.
+///
+/// [`Debug`] is required so that encoders and decoders using the engine
+/// can be debug-printed. It should show which engine is used
+/// but not its lookup tables, which are large.
+///
+/// [`Debug`]: fmt::Debug
+///
+/// # Object safety
+///
+/// [`Engine`] is object safe so engines of different types can be used
+/// via `dyn Engine`, e.g. stored in a `Vec<Box<dyn Engine>>`.
+/// This is how [`DefaultEngine`] selects an engine at runtime.
+///
+/// Methods with `where Self: Sized` bound, i.e. [`Engine::eval_poly`]
+/// and [`Engine::eval_poly_in_field`], can't be called via `dyn Engine`.
+///
+/// ```rust
+/// use simd_erasure_core::engine::{Engine, Naive, NoSimd};
+///
+/// let engines: Vec<Box<dyn Engine>> = vec![Box::new(Naive::new()), Box::new(NoSimd::new())];
+///
+/// for engine in &engines {
+///     let mut x = vec![[1u8; 64]; 4];
+///     engine.mul(&mut x, 12345);
+/// }
+/// ```
+pub trait Engine: fmt::Debug {
     // ============================================================
     // REQUIRED
 
@@ -109,6 +151,8 @@ pub trait Engine {
     ///
     /// - FFT is done on chunk `data[pos .. pos + size]`
     /// - `size` must be `2^n`
+    /// - `truncated_size` must be `<= size`
+    /// - `pos + size` must be `<= data.len()`
     /// - Before function call `data[pos .. pos + size]` must be valid.
     /// - After function call
     ///     - `data[pos .. pos + truncated_size]`
@@ -116,6 +160,18 @@ pub trait Engine {
     ///     - `data[pos + truncated_size .. pos + size]`
     ///       contains valid FFT result if this contained
     ///       only `0u8`:s and garbage otherwise.
+    ///
+    /// As `size` is always a power of two, there is no code path for
+    /// other sizes which a power-of-two variant could leave out.
+    /// `truncated_size` only bounds which butterfly groups are run,
+    /// so with `truncated_size == size` this is the full FFT at no extra cost.
+    ///
+    /// Above requirements are only checked with debug assertions,
+    /// use [`EngineExt::fft_checked`] to always check them.
+    /// `skew_delta` is used to index [`Skew`] table
+    /// and is always bounds checked.
+    ///
+    /// [`Skew`]: crate::engine::tables::Skew
     fn fft(
         &self,
         data: &mut ShardsRefMut,
@@ -129,6 +185,8 @@ pub trait Engine {
     ///
     /// - IFFT is done on chunk `data[pos .. pos + size]`
     /// - `size` must be `2^n`
+    /// - `truncated_size` must be `<= size`
+    /// - `pos + size` must be `<= data.len()`
     /// - Before function call `data[pos .. pos + size]` must be valid.
     /// - After function call
     ///     - `data[pos .. pos + truncated_size]`
@@ -136,6 +194,20 @@ pub trait Engine {
     ///     - `data[pos + truncated_size .. pos + size]`
     ///       contains valid IFFT result if this contained
     ///       only `0u8`:s and garbage otherwise.
+    ///
+    /// This IFFT needs no normalization: With full `truncated_size`
+    /// [`Engine::fft`] with same arguments is its exact inverse,
+    /// there is no scaling by size or position like with complex FFT.
+    /// Decoding multiplies shards only by erasure locator values,
+    /// received shards before IFFT and restored shards after FFT,
+    /// so there is no multiplication after IFFT to fuse into it.
+    ///
+    /// Above requirements are only checked with debug assertions,
+    /// use [`EngineExt::ifft_checked`] to always check them.
+    /// `skew_delta` is used to index [`Skew`] table
+    /// and is always bounds checked.
+    ///
+    /// [`Skew`]: crate::engine::tables::Skew
     fn ifft(
         &self,
         data: &mut ShardsRefMut,
@@ -146,21 +218,1045 @@ pub trait Engine {
     );
 
     /// `x[] *= log_m`
+    ///
+    /// To multiply shards with different `log_m` call this once per shard.
+    /// Each 64-byte chunk is multiplied independently of others,
+    /// so already a single shard gives SIMD engines enough independent work
+    /// and there is no separate batched variant.
+    ///
+    /// `log_m` is logarithm of a non-zero multiplier, so multiplying
+    /// by zero can't be expressed here and there is no zero special case.
+    /// Both `0` and [`GF_MODULUS`] multiply by one. Callers which want to
+    /// skip multiplying by one check for it themselves,
+    /// like [`Engine::fft_butterfly_one_layer`] does for [`GF_MODULUS`].
+    /// Engines use table lookups, so time taken doesn't depend on `log_m`.
     fn mul(&self, x: &mut [[u8; 64]], log_m: GfElement);
 
     // ============================================================
     // PROVIDED
 
+    /// `dst[] ^= src[]`
+    ///
+    /// Default implementation is a plain loop over 64-byte chunks,
+    /// SIMD engines override this with vectorized XOR.
+    ///
+    /// This is the addition of Galois field elements, so it can also
+    /// be used on its own, e.g. for simple XOR parity.
+    ///
+    /// - `dst` and `src` must have same length.
+    fn add_xor(&self, dst: &mut [[u8; 64]], src: &[[u8; 64]]) {
+        utils::xor(dst, src);
+    }
+
     /// Evaluate polynomial.
+    ///
+    /// `erasures` contains `1` at indexes of missing shards and `0` elsewhere,
+    /// which is overwritten with the error locator polynomial evaluated
+    /// at every element of the field.
+    ///
+    /// `truncated_size` is the partial evaluation: Only
+    /// `erasures[.. truncated_size]` may be non-zero, so the first
+    /// Walsh-Hadamard transform can skip the rest. Decoders pass the end
+    /// of the range of shards they use, e.g. `original_end` in high rate.
+    /// Output is always calculated for all `GF_ORDER` elements since
+    /// the second transform needs every input element.
     fn eval_poly(erasures: &mut [GfElement; GF_ORDER], truncated_size: usize)
     where
         Self: Sized,
     {
         utils::eval_poly(erasures, truncated_size);
     }
+
+    /// Evaluate polynomial in the [`GfField`] of this engine.
+    ///
+    /// Default implementation calls [`Engine::eval_poly`],
+    /// engines supporting a custom [`GfField`] override this.
+    fn eval_poly_in_field(&self, erasures: &mut [GfElement; GF_ORDER], truncated_size: usize)
+    where
+        Self: Sized,
+    {
+        Self::eval_poly(erasures, truncated_size);
+    }
+
+    /// One layer of FFT butterflies with distance `dist`
+    /// and same factor `log_m` for every butterfly.
+    ///
+    /// `shards` is processed in groups of `2 * dist` shards.
+    /// Within each group, for `i` in `0..dist`, shards `x = shards[i]`
+    /// and `y = shards[i + dist]` are transformed as
+    /// `x[] ^= y[] * log_m` followed by `y[] ^= x[]`,
+    /// except that multiplication is skipped if `log_m == GF_MODULUS`.
+    ///
+    /// This is a building block for custom transforms,
+    /// e.g. FFT of non-power-of-two size or with interleaved shards.
+    /// [`Engine::fft`] does the same as `log2(size)` of these layers
+    /// with `log_m` from [`Skew`] table for each group,
+    /// but is much faster, so use it when possible.
+    ///
+    /// Default implementation uses [`Engine::xor_then_mul`]
+    /// and [`Engine::add_xor`].
+    ///
+    /// # Panics
+    ///
+    /// If `dist` is zero or `shards.len()` isn't a multiple of `2 * dist`.
+    ///
+    /// [`Skew`]: crate::engine::tables::Skew
+    fn fft_butterfly_one_layer(&self, shards: &mut ShardsRefMut, dist: usize, log_m: GfElement) {
+        assert!(dist > 0 && shards.len() % (dist * 2) == 0);
+
+        let mut r = 0;
+        while r < shards.len() {
+            for i in r..r + dist {
+                let (x, y) = shards.dist2_mut(i, dist);
+
+                if log_m != GF_MODULUS {
+                    self.xor_then_mul(x, y, log_m);
+                }
+                self.add_xor(y, x);
+            }
+            r += dist * 2;
+        }
+    }
+
+    /// Same as [`Engine::fft`] except that given `skew` factors are used
+    /// instead of the [`Skew`] table of this engine.
+    ///
+    /// `skew[i]` is used where [`Engine::fft`] would use `Skew[skew_delta + i]`,
+    /// so `fft(data, pos, size, truncated_size, skew_delta)` is same as
+    /// `fft_with_skew(data, pos, size, truncated_size, &Skew[skew_delta..])`.
+    /// This allows custom Reed-Solomon variants with their own skew factors.
+    ///
+    /// Default implementation is a plain FFT using [`Engine::xor_then_mul`]
+    /// and [`Engine::add_xor`]. [`Naive`] and [`NoSimd`] override this
+    /// with their own FFT.
+    ///
+    /// # Panics
+    ///
+    /// If `skew.len() < size - 1`.
+    ///
+    /// [`Skew`]: crate::engine::tables::Skew
+    fn fft_with_skew(
+        &self,
+        data: &mut ShardsRefMut,
+        pos: usize,
+        size: usize,
+        truncated_size: usize,
+        skew: &[GfElement],
+    ) {
+        debug_assert!(size.is_power_of_two());
+        debug_assert!(truncated_size <= size);
+        debug_assert!(pos + size <= data.len());
+        assert!(skew.len() + 1 >= size);
+
+        let mut dist = size / 2;
+        while dist > 0 {
+            let mut r = 0;
+            while r < truncated_size {
+                let log_m = skew[r + dist - 1];
+                for i in r..r + dist {
+                    let (a, b) = data.dist2_mut(pos + i, dist);
+
+                    if log_m != GF_MODULUS {
+                        self.xor_then_mul(a, b, log_m);
+                    }
+                    self.add_xor(b, a);
+                }
+                r += dist * 2;
+            }
+            dist /= 2;
+        }
+    }
+
+    /// Returns Galois field polynomial used by this engine,
+    /// including the `x^16` term, e.g. [`GF_POLYNOMIAL`] `0x1002D`.
+    ///
+    /// Shards encoded with one engine can be decoded with another engine
+    /// only if both return the same polynomial.
+    ///
+    /// This is a method instead of an associated constant
+    /// so that [`Engine`] stays object safe and so that engines
+    /// with a custom [`GfField`] can return their own polynomial.
+    ///
+    /// Default implementation returns [`GF_POLYNOMIAL`]
+    /// which all engines use unless created with a custom [`GfField`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simd_erasure_core::engine::{Engine, Naive, NoSimd};
+    ///
+    /// assert_eq!(NoSimd::new().gf_polynomial(), Naive::new().gf_polynomial());
+    /// ```
+    fn gf_polynomial(&self) -> u32 {
+        GF_POLYNOMIAL as u32
+    }
+
+    /// Returns `true` if `x` contains only `0u8`:s.
+    ///
+    /// Multiplying such shard by anything gives zero,
+    /// so e.g. multiplication can be skipped for it.
+    ///
+    /// Default implementation compares 64-byte chunks in a plain loop,
+    /// SIMD engines may override this.
+    fn is_zero_shard(&self, x: &[[u8; 64]]) -> bool {
+        x.iter().all(|chunk| chunk == &[0; 64])
+    }
+
+    /// `x[i] = 1 / x[i]` for each Galois field element `x[i]` of `x`,
+    /// except that `0` stays `0`.
+    ///
+    /// Unlike [`Engine::mul`], which multiplies by a constant
+    /// given as logarithm, this inverts each element separately,
+    /// e.g. for dividing by data-dependent elements.
+    ///
+    /// Default implementation uses [`Exp`] and [`Log`] tables
+    /// of the default field, see [`utils::mul_inverse_shard_with_exp_log`].
+    /// Inversion is a lookup into a 64k-entry table
+    /// so it doesn't map to 16-entry SIMD shuffles.
+    ///
+    /// [`Exp`]: crate::engine::tables::Exp
+    /// [`Log`]: crate::engine::tables::Log
+    fn mul_inverse_shard(&self, x: &mut [[u8; 64]]) {
+        utils::mul_inverse_shard_with_exp_log(x, tables::get_exp_log());
+    }
+
+    /// Returns shard size multiple in bytes which this engine
+    /// processes most efficiently.
+    ///
+    /// Shards can have any even size, but internally they are
+    /// processed in 64-byte chunks, so with other sizes
+    /// part of the work is done on padding.
+    /// Use [`round_shard_bytes`] to round shard size up to this multiple.
+    ///
+    /// This is a method instead of an associated constant
+    /// so that [`Engine`] stays object safe.
+    ///
+    /// Default implementation returns `64`.
+    fn preferred_shard_len_multiple(&self) -> usize {
+        64
+    }
+
+    /// Returns `true` if [`Engine::mul`] of this engine is consistent,
+    /// i.e. its lookup tables and multiplication code seem to be correct.
+    ///
+    /// This is a quick spot-check e.g. for program startup
+    /// on an unusual platform, not an exhaustive test.
+    /// Using a few values of `log_m` it checks that
+    /// - `log_m = 0` doesn't change data and zero stays zero.
+    /// - multiplying by `log_m` changes data and multiplying then
+    ///   by [`utils::mul_inverse`]`(log_m)` restores it.
+    /// - multiplying by `log_m` and then by `log_n` is same as
+    ///   multiplying once by `log_m + log_n` modulo [`GF_MODULUS`].
+    /// - multiplication distributes over XOR.
+    ///
+    /// [`DefaultEngine::new`] runs this when debug assertions are enabled.
+    fn verify_tables(&self) -> bool {
+        const LOG_MS: [GfElement; 4] = [1, 2, 12345, GF_MODULUS - 1];
+
+        let mul = |x: [u8; 64], log_m| {
+            let mut x = [x];
+            self.mul(&mut x, log_m);
+            x[0]
+        };
+
+        let xor = |x: [u8; 64], y: [u8; 64]| -> [u8; 64] { core::array::from_fn(|i| x[i] ^ y[i]) };
+
+        let a: [u8; 64] = core::array::from_fn(|i| (i as u8).wrapping_mul(151).wrapping_add(7));
+        let b: [u8; 64] = core::array::from_fn(|i| (i as u8).wrapping_mul(73) ^ 0xA5);
+        let zero = [0u8; 64];
+
+        if mul(a, 0) != a || mul(zero, 0) != zero {
+            return false;
+        }
+
+        LOG_MS.iter().all(|&log_m| {
+            let product = mul(a, log_m);
+
+            product != a
+                && mul(zero, log_m) == zero
+                && mul(product, utils::mul_inverse(log_m)) == a
+                && mul(xor(a, b), log_m) == xor(product, mul(b, log_m))
+                && LOG_MS
+                    .iter()
+                    .all(|&log_n| mul(product, log_n) == mul(a, utils::add_mod(log_m, log_n)))
+        })
+    }
+
+    /// `x[] ^= y[] * log_m`
+    ///
+    /// This is the multiply-and-add step of FFT/IFFT butterflies.
+    /// Default implementation calls [`Engine::mul`] on a temporary copy
+    /// of `y` and then XORs that into `x`, engines which can fuse
+    /// these into a single pass override this.
+    ///
+    /// - `x` and `y` must have same length.
+    fn xor_then_mul(&self, x: &mut [[u8; 64]], y: &[[u8; 64]], log_m: GfElement) {
+        debug_assert_eq!(x.len(), y.len());
+
+        let mut tmp = [[0u8; 64]; 16];
+        for (x, y) in core::iter::zip(x.chunks_mut(16), y.chunks(16)) {
+            let tmp = &mut tmp[..y.len()];
+            tmp.copy_from_slice(y);
+            self.mul(tmp, log_m);
+            utils::xor(x, tmp);
+        }
+    }
+}
+
+// ======================================================================
+// EngineExt - PUBLIC
+
+/// Convenience methods for [`Engine`].
+///
+/// This is implemented for all [`Engine`]:s.
+pub trait EngineExt: Engine {
+    /// Same as [`Engine::fft`] except that requirements
+    /// of its arguments are always checked.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simd_erasure_core::engine::{EngineError, EngineExt, NoSimd, ShardsRefMut};
+    ///
+    /// let engine = NoSimd::new();
+    /// let mut data = vec![[1u8; 64]; 8];
+    /// let mut shards = ShardsRefMut::new(8, 1, &mut data);
+    ///
+    /// assert_eq!(engine.fft_checked(&mut shards, 0, 8, 8, 8), Ok(()));
+    /// assert_eq!(
+    ///     engine.fft_checked(&mut shards, 0, 6, 6, 6),
+    ///     Err(EngineError::SizeNotPowerOfTwo { size: 6 })
+    /// );
+    /// ```
+    fn fft_checked(
+        &self,
+        data: &mut ShardsRefMut,
+        pos: usize,
+        size: usize,
+        truncated_size: usize,
+        skew_delta: usize,
+    ) -> Result<(), EngineError> {
+        check_fft_args(data.len(), pos, size, truncated_size)?;
+        self.fft(data, pos, size, truncated_size, skew_delta);
+        Ok(())
+    }
+
+    /// In-place FFT of whole chunk `data[0 .. size]`.
+    ///
+    /// This is same as `fft(data, 0, size, size, size)`,
+    /// see [`Engine::fft`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simd_erasure_core::engine::{EngineExt, NoSimd, ShardsRefMut};
+    ///
+    /// let engine = NoSimd::new();
+    /// let mut data = vec![[1u8; 64]; 8];
+    /// let mut shards = ShardsRefMut::new(8, 1, &mut data);
+    ///
+    /// engine.fft_simple(&mut shards, 8);
+    /// engine.ifft_simple(&mut shards, 8);
+    ///
+    /// assert_eq!(data, vec![[1u8; 64]; 8]);
+    /// ```
+    fn fft_simple(&self, data: &mut ShardsRefMut, size: usize) {
+        self.fft(data, 0, size, size, size);
+    }
+
+    /// Same as [`Engine::ifft`] except that requirements
+    /// of its arguments are always checked.
+    ///
+    /// See [`EngineExt::fft_checked`] for an example.
+    fn ifft_checked(
+        &self,
+        data: &mut ShardsRefMut,
+        pos: usize,
+        size: usize,
+        truncated_size: usize,
+        skew_delta: usize,
+    ) -> Result<(), EngineError> {
+        check_fft_args(data.len(), pos, size, truncated_size)?;
+        self.ifft(data, pos, size, truncated_size, skew_delta);
+        Ok(())
+    }
+
+    /// In-place IFFT of whole chunk `data[0 .. size]`.
+    ///
+    /// This is same as `ifft(data, 0, size, size, size)`,
+    /// see [`Engine::ifft`] for details.
+    ///
+    /// See [`EngineExt::fft_simple`] for an example.
+    fn ifft_simple(&self, data: &mut ShardsRefMut, size: usize) {
+        self.ifft(data, 0, size, size, size);
+    }
+}
+
+impl<E: Engine + ?Sized> EngineExt for E {}
+
+// ======================================================================
+// EngineError - PUBLIC
+
+/// Invalid arguments given to [`EngineExt::fft_checked`]
+/// or [`EngineExt::ifft_checked`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EngineError {
+    /// `pos + size` is larger than number of shards.
+    OutOfBounds {
+        /// Given `pos`.
+        pos: usize,
+        /// Given `size`.
+        size: usize,
+        /// Number of shards.
+        len: usize,
+    },
+
+    /// `size` isn't a power of two.
+    SizeNotPowerOfTwo {
+        /// Given `size`.
+        size: usize,
+    },
+
+    /// `truncated_size` is larger than `size`.
+    TruncatedSizeTooLarge {
+        /// Given `truncated_size`.
+        truncated_size: usize,
+        /// Given `size`.
+        size: usize,
+    },
+}
+
+// ======================================================================
+// EngineError - IMPL DISPLAY
+
+impl fmt::Display for EngineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfBounds { pos, size, len } => {
+                write!(
+                    f,
+                    "out of bounds: pos {pos} + size {size} is larger than shard count {len}"
+                )
+            }
+
+            Self::SizeNotPowerOfTwo { size } => {
+                write!(f, "size {size} is not a power of two")
+            }
+
+            Self::TruncatedSizeTooLarge {
+                truncated_size,
+                size,
+            } => {
+                write!(
+                    f,
+                    "truncated size {truncated_size} is larger than size {size}"
+                )
+            }
+        }
+    }
+}
+
+// ======================================================================
+// EngineError - IMPL ERROR
+
+impl core::error::Error for EngineError {}
+
+// ======================================================================
+// FUNCTIONS - PUBLIC
+
+/// Rounds `shard_bytes` up to [`Engine::preferred_shard_len_multiple`]
+/// of given `engine`.
+///
+/// # Examples
+///
+/// ```rust
+/// use simd_erasure_core::engine::{self, NoSimd};
+///
+/// assert_eq!(engine::round_shard_bytes(1000, &NoSimd::new()), 1024);
+/// assert_eq!(engine::round_shard_bytes(1024, &NoSimd::new()), 1024);
+/// ```
+pub fn round_shard_bytes<E: Engine + ?Sized>(shard_bytes: usize, engine: &E) -> usize {
+    shard_bytes.next_multiple_of(engine.preferred_shard_len_multiple())
+}
+
+// ======================================================================
+// FUNCTIONS - PRIVATE
+
+fn check_fft_args(
+    len: usize,
+    pos: usize,
+    size: usize,
+    truncated_size: usize,
+) -> Result<(), EngineError> {
+    if !size.is_power_of_two() {
+        Err(EngineError::SizeNotPowerOfTwo { size })
+    } else if truncated_size > size {
+        Err(EngineError::TruncatedSizeTooLarge {
+            truncated_size,
+            size,
+        })
+    } else if pos.checked_add(size).is_none_or(|end| end > len) {
+        Err(EngineError::OutOfBounds { pos, size, len })
+    } else {
+        Ok(())
+    }
 }
 
 // ======================================================================
 // TESTS
 
 // Engines are tested indirectly via roundtrip tests of HighRate and LowRate.
+
+#[cfg(test)]
+mod tests {
+    use alloc::{boxed::Box, format, vec, vec::Vec};
+
+    use super::*;
+
+    fn generate_chunks(count: usize, seed: u8) -> Vec<[u8; 64]> {
+        (0..count)
+            .map(|i| core::array::from_fn(|j| (i * 64 + j) as u8 ^ seed.wrapping_mul(37)))
+            .collect()
+    }
+
+    fn test_xor_then_mul<E: Engine>(engine: &E) {
+        for count in [1, 15, 16, 17, 40] {
+            for log_m in [0, 1, 12345, GF_MODULUS - 1] {
+                let y = generate_chunks(count, 1);
+                let mut x = generate_chunks(count, 2);
+                let mut expected = x.clone();
+
+                let mut product = y.clone();
+                Naive::new().mul(&mut product, log_m);
+                utils::xor(&mut expected, &product);
+
+                engine.xor_then_mul(&mut x, &y, log_m);
+                assert_eq!(x, expected);
+            }
+        }
+    }
+
+    fn test_add_xor<E: Engine>(engine: &E) {
+        for count in [0, 1, 3, 40] {
+            let src = generate_chunks(count, 1);
+            let mut dst = generate_chunks(count, 2);
+            let mut expected = dst.clone();
+
+            utils::xor(&mut expected, &src);
+
+            engine.add_xor(&mut dst, &src);
+            assert_eq!(dst, expected);
+        }
+    }
+
+    #[test]
+    fn add_xor() {
+        test_add_xor(&Naive::new());
+        test_add_xor(&NoSimd::new());
+        test_add_xor(&DefaultEngine::new());
+
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            cpufeatures::new!(has_avx2, "avx2");
+            if has_avx2::get() {
+                test_add_xor(&Avx2::new());
+            }
+
+            cpufeatures::new!(has_ssse3, "ssse3");
+            if has_ssse3::get() {
+                test_add_xor(&Ssse3::new());
+            }
+        }
+
+        #[cfg(target_arch = "aarch64")]
+        {
+            cpufeatures::new!(has_neon, "neon");
+            if has_neon::get() {
+                test_add_xor(&Neon::new());
+            }
+        }
+    }
+
+    #[test]
+    fn debug() {
+        assert_eq!(format!("{:?}", Naive::new()), "Naive { .. }");
+        assert_eq!(
+            format!("{:?}", NoSimd::new()),
+            format!("NoSimd {{ field: {:?}, .. }}", GfField::DEFAULT)
+        );
+
+        let field = GfField::new(GfPolynomial(0x100B), 2).unwrap();
+        assert_eq!(
+            format!("{:?}", NoSimd::new_with_field(field)),
+            format!("NoSimd {{ field: {field:?}, .. }}")
+        );
+
+        assert!(format!("{:?}", DefaultEngine::new()).starts_with("DefaultEngine("));
+    }
+
+    #[test]
+    fn dyn_engine() {
+        let engines: Vec<Box<dyn Engine>> = vec![
+            Box::new(Naive::new()),
+            Box::new(NoSimd::new()),
+            Box::new(DefaultEngine::new()),
+        ];
+
+        let engine = NoSimd::new();
+        let mut expected = generate_chunks(8 * 2, 0);
+        let mut shards = ShardsRefMut::new(8, 2, &mut expected);
+        engine.fft(&mut shards, 0, 8, 8, 8);
+        engine.mul(&mut shards[3], 12345);
+        engine.ifft(&mut shards, 0, 8, 8, 8);
+
+        for dyn_engine in &engines {
+            let mut data = generate_chunks(8 * 2, 0);
+            let mut shards = ShardsRefMut::new(8, 2, &mut data);
+            dyn_engine.fft(&mut shards, 0, 8, 8, 8);
+            dyn_engine.mul(&mut shards[3], 12345);
+            dyn_engine.ifft(&mut shards, 0, 8, 8, 8);
+            assert_eq!(data, expected);
+
+            assert_eq!(dyn_engine.gf_polynomial(), engine.gf_polynomial());
+        }
+    }
+
+    #[test]
+    fn fft_checked() {
+        let engine = NoSimd::new();
+        let mut data = generate_chunks(8, 1);
+        let mut expected = data.clone();
+
+        engine.fft(&mut ShardsRefMut::new(8, 1, &mut expected), 4, 4, 3, 4);
+        engine.ifft(&mut ShardsRefMut::new(8, 1, &mut expected), 0, 4, 4, 0);
+
+        let mut shards = ShardsRefMut::new(8, 1, &mut data);
+        assert_eq!(engine.fft_checked(&mut shards, 4, 4, 3, 4), Ok(()));
+        assert_eq!(engine.ifft_checked(&mut shards, 0, 4, 4, 0), Ok(()));
+        assert_eq!(data, expected);
+
+        let mut shards = ShardsRefMut::new(8, 1, &mut data);
+        assert_eq!(
+            engine.fft_checked(&mut shards, 0, 3, 3, 0),
+            Err(EngineError::SizeNotPowerOfTwo { size: 3 })
+        );
+        assert_eq!(
+            engine.ifft_checked(&mut shards, 0, 4, 5, 0),
+            Err(EngineError::TruncatedSizeTooLarge {
+                truncated_size: 5,
+                size: 4
+            })
+        );
+        assert_eq!(
+            engine.fft_checked(&mut shards, 6, 4, 4, 0),
+            Err(EngineError::OutOfBounds {
+                pos: 6,
+                size: 4,
+                len: 8
+            })
+        );
+        assert_eq!(data, expected);
+    }
+
+    #[test]
+    fn eval_poly_truncated() {
+        for truncated_size in [1, 100, 4096, GF_ORDER] {
+            let mut erasures = [0; GF_ORDER];
+            for i in (0..truncated_size).step_by(3) {
+                erasures[i] = 1;
+            }
+            let mut expected = erasures;
+
+            NoSimd::eval_poly(&mut erasures, truncated_size);
+            Naive::eval_poly(&mut expected, GF_ORDER);
+            assert_eq!(erasures, expected);
+        }
+    }
+
+    #[test]
+    fn fft_truncated_is_prefix_of_full_fft() {
+        let engines: [&dyn Engine; 3] = [&Naive::new(), &NoSimd::new(), &DefaultEngine::new()];
+
+        for engine in engines {
+            for (size, truncated_size) in [(2, 1), (16, 11), (32, 17), (64, 64)] {
+                let mut full = generate_chunks(size * 2, 4);
+                let mut truncated = full.clone();
+
+                engine.fft(
+                    &mut ShardsRefMut::new(size, 2, &mut full),
+                    0,
+                    size,
+                    size,
+                    size,
+                );
+                engine.fft(
+                    &mut ShardsRefMut::new(size, 2, &mut truncated),
+                    0,
+                    size,
+                    truncated_size,
+                    size,
+                );
+                assert_eq!(truncated[..truncated_size * 2], full[..truncated_size * 2]);
+            }
+        }
+    }
+
+    #[test]
+    fn fft_is_inverse_of_ifft() {
+        let engines: [&dyn Engine; 3] = [&Naive::new(), &NoSimd::new(), &DefaultEngine::new()];
+
+        for engine in engines {
+            for (size, skew_delta) in [(2, 0), (16, 16), (64, 64)] {
+                let expected = generate_chunks(size * 2, 5);
+                let mut data = expected.clone();
+
+                let mut shards = ShardsRefMut::new(size, 2, &mut data);
+                engine.ifft(&mut shards, 0, size, size, skew_delta);
+                engine.fft(&mut shards, 0, size, size, skew_delta);
+                assert_eq!(data, expected);
+            }
+        }
+    }
+
+    fn test_fft_with_skew<E: Engine>(engine: &E) {
+        let skew = tables::get_skew();
+
+        for (size, truncated_size, skew_delta) in [(1, 1, 0), (8, 8, 8), (16, 11, 16), (32, 5, 64)]
+        {
+            let mut expected = generate_chunks(32, 1);
+            let mut actual = expected.clone();
+
+            Naive::new().fft(
+                &mut ShardsRefMut::new(32, 1, &mut expected),
+                0,
+                size,
+                truncated_size,
+                skew_delta,
+            );
+            engine.fft_with_skew(
+                &mut ShardsRefMut::new(32, 1, &mut actual),
+                0,
+                size,
+                truncated_size,
+                &skew[skew_delta..],
+            );
+            assert_eq!(actual[..truncated_size], expected[..truncated_size]);
+        }
+
+        // Custom skew factors, compared to the default implementation.
+        let custom: Vec<GfElement> = (0..15).map(|i| i * 4099 % GF_MODULUS).collect();
+        let mut expected = generate_chunks(16, 2);
+        let mut actual = expected.clone();
+
+        let dyn_engine: &dyn Engine = &DefaultEngine::new();
+        dyn_engine.fft_with_skew(
+            &mut ShardsRefMut::new(16, 1, &mut expected),
+            0,
+            16,
+            16,
+            &custom,
+        );
+        engine.fft_with_skew(
+            &mut ShardsRefMut::new(16, 1, &mut actual),
+            0,
+            16,
+            16,
+            &custom,
+        );
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn fft_simple_matches_naive() {
+        let mut engines: Vec<Box<dyn Engine>> =
+            vec![Box::new(NoSimd::new()), Box::new(DefaultEngine::new())];
+
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            cpufeatures::new!(has_avx2, "avx2");
+            if has_avx2::get() {
+                engines.push(Box::new(Avx2::new()));
+            }
+
+            cpufeatures::new!(has_ssse3, "ssse3");
+            if has_ssse3::get() {
+                engines.push(Box::new(Ssse3::new()));
+            }
+        }
+
+        for engine in engines {
+            for (size, shard_len_64) in [(1, 1), (4, 1), (32, 1), (64, 2)] {
+                let original = generate_chunks(size * shard_len_64, 7);
+
+                let mut expected = original.clone();
+                let mut actual = original.clone();
+                Naive::new().fft(
+                    &mut ShardsRefMut::new(size, shard_len_64, &mut expected),
+                    0,
+                    size,
+                    size,
+                    size,
+                );
+                engine.fft_simple(
+                    &mut ShardsRefMut::new(size, shard_len_64, &mut actual),
+                    size,
+                );
+                assert_eq!(actual, expected, "{engine:?} fft, size = {size}");
+
+                let mut expected = original.clone();
+                let mut actual = original;
+                Naive::new().ifft(
+                    &mut ShardsRefMut::new(size, shard_len_64, &mut expected),
+                    0,
+                    size,
+                    size,
+                    size,
+                );
+                engine.ifft_simple(
+                    &mut ShardsRefMut::new(size, shard_len_64, &mut actual),
+                    size,
+                );
+                assert_eq!(actual, expected, "{engine:?} ifft, size = {size}");
+            }
+        }
+    }
+
+    // FFT composed of single layers, same as `Naive::fft` with full size.
+    fn fft_by_layers<E: Engine + ?Sized>(
+        engine: &E,
+        data: &mut ShardsRefMut,
+        size: usize,
+        skew_delta: usize,
+    ) {
+        let skew = tables::get_skew();
+
+        let mut dist = size / 2;
+        while dist > 0 {
+            for r in (0..size).step_by(dist * 2) {
+                let (_, mut rest) = data.split_at_mut(r);
+                let (mut group, _) = rest.split_at_mut(dist * 2);
+                engine.fft_butterfly_one_layer(&mut group, dist, skew[r + dist + skew_delta - 1]);
+            }
+            dist /= 2;
+        }
+    }
+
+    #[test]
+    fn fft_butterfly_one_layer() {
+        let engines: [&dyn Engine; 3] = [&Naive::new(), &NoSimd::new(), &DefaultEngine::new()];
+
+        for engine in engines {
+            for (size, skew_delta) in [(2, 0), (16, 16), (32, 64)] {
+                let mut expected = generate_chunks(size * 2, 3);
+                let mut actual = expected.clone();
+
+                Naive::new().fft(
+                    &mut ShardsRefMut::new(size, 2, &mut expected),
+                    0,
+                    size,
+                    size,
+                    skew_delta,
+                );
+                fft_by_layers(
+                    engine,
+                    &mut ShardsRefMut::new(size, 2, &mut actual),
+                    size,
+                    skew_delta,
+                );
+                assert_eq!(actual, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn fft_with_skew() {
+        test_fft_with_skew(&Naive::new());
+        test_fft_with_skew(&NoSimd::new());
+        test_fft_with_skew(&DefaultEngine::new());
+    }
+
+    #[test]
+    fn gf_polynomial() {
+        let expected = GF_POLYNOMIAL as u32;
+
+        assert_eq!(Naive::new().gf_polynomial(), expected);
+        assert_eq!(NoSimd::new().gf_polynomial(), expected);
+        assert_eq!(DefaultEngine::new().gf_polynomial(), expected);
+
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            assert_eq!(Avx2::new().gf_polynomial(), expected);
+            assert_eq!(Ssse3::new().gf_polynomial(), expected);
+        }
+
+        #[cfg(target_arch = "aarch64")]
+        assert_eq!(Neon::new().gf_polynomial(), expected);
+
+        let field = GfField::new(GfPolynomial(0x100B), 2).unwrap();
+        assert_eq!(NoSimd::new_with_field(field).gf_polynomial(), 0x1100B);
+        assert_eq!(
+            NoSimd::new_with_field(GfField::DEFAULT).gf_polynomial(),
+            expected
+        );
+    }
+
+    #[test]
+    fn preferred_shard_len_multiple() {
+        let mut engines: Vec<Box<dyn Engine>> = vec![
+            Box::new(Naive::new()),
+            Box::new(NoSimd::new()),
+            Box::new(DefaultEngine::new()),
+        ];
+
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            cpufeatures::new!(has_avx2, "avx2");
+            if has_avx2::get() {
+                engines.push(Box::new(Avx2::new()));
+            }
+
+            cpufeatures::new!(has_ssse3, "ssse3");
+            if has_ssse3::get() {
+                engines.push(Box::new(Ssse3::new()));
+            }
+        }
+
+        for engine in engines {
+            let multiple = engine.preferred_shard_len_multiple();
+            assert!(multiple >= 2 && multiple % 2 == 0);
+
+            assert_eq!(round_shard_bytes(0, &*engine), 0);
+            assert_eq!(round_shard_bytes(2, &*engine), multiple);
+            assert_eq!(round_shard_bytes(multiple, &*engine), multiple);
+            assert_eq!(round_shard_bytes(multiple + 2, &*engine), multiple * 2);
+        }
+    }
+
+    #[test]
+    fn mul_inverse_shard() {
+        let engines: [Box<dyn Engine>; 3] = [
+            Box::new(Naive::new()),
+            Box::new(NoSimd::new()),
+            Box::new(DefaultEngine::new()),
+        ];
+
+        let original: [[u8; 64]; 2] =
+            core::array::from_fn(|i| core::array::from_fn(|j| (i * 64 + j) as u8 ^ 0x5A));
+
+        for engine in engines {
+            let mut inverse = original;
+            engine.mul_inverse_shard(&mut inverse);
+            assert_ne!(inverse, original);
+
+            let mut x = inverse;
+            engine.mul_inverse_shard(&mut x);
+            assert_eq!(x, original);
+
+            // 1 / (x * m) * m == 1 / x
+            for log_m in 0..GF_MODULUS {
+                let mut x = original;
+                engine.mul(&mut x, log_m);
+                engine.mul_inverse_shard(&mut x);
+                engine.mul(&mut x, log_m);
+                assert_eq!(x, inverse, "log_m = {log_m}");
+            }
+        }
+    }
+
+    #[test]
+    fn mul_by_one() {
+        let engines: Vec<Box<dyn Engine>> = vec![
+            Box::new(Naive::new()),
+            Box::new(NoSimd::new()),
+            Box::new(DefaultEngine::new()),
+        ];
+
+        let original = generate_chunks(3, 1);
+        for engine in engines {
+            for log_m in [0, GF_MODULUS] {
+                let mut x = original.clone();
+                engine.mul(&mut x, log_m);
+                assert_eq!(x, original, "log_m = {log_m}");
+            }
+        }
+    }
+
+    #[test]
+    fn is_zero_shard() {
+        let mut engines: Vec<Box<dyn Engine>> = vec![
+            Box::new(Naive::new()),
+            Box::new(NoSimd::new()),
+            Box::new(DefaultEngine::new()),
+        ];
+
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            cpufeatures::new!(has_avx2, "avx2");
+            if has_avx2::get() {
+                engines.push(Box::new(Avx2::new()));
+            }
+        }
+
+        #[cfg(target_arch = "aarch64")]
+        {
+            cpufeatures::new!(has_neon, "neon");
+            if has_neon::get() {
+                engines.push(Box::new(Neon::new()));
+            }
+        }
+
+        for engine in engines {
+            assert!(engine.is_zero_shard(&[]));
+            assert!(engine.is_zero_shard(&[[0; 64]; 3]));
+
+            for (chunk, byte) in [(0, 0), (0, 63), (1, 31), (2, 32), (2, 63)] {
+                let mut x = [[0; 64]; 3];
+                x[chunk][byte] = 1;
+                assert!(!engine.is_zero_shard(&x));
+            }
+        }
+    }
+
+    #[test]
+    fn verify_tables() {
+        assert!(Naive::new().verify_tables());
+        assert!(NoSimd::new().verify_tables());
+        assert!(DefaultEngine::new().verify_tables());
+
+        let field = GfField::new(GfPolynomial(0x100B), 2).unwrap();
+        assert!(NoSimd::new_with_field(field).verify_tables());
+
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            cpufeatures::new!(has_avx2, "avx2");
+            if has_avx2::get() {
+                assert!(Avx2::new().verify_tables());
+            }
+
+            cpufeatures::new!(has_ssse3, "ssse3");
+            if has_ssse3::get() {
+                assert!(Ssse3::new().verify_tables());
+            }
+        }
+    }
+
+    #[test]
+    fn xor_then_mul() {
+        test_xor_then_mul(&Naive::new());
+        test_xor_then_mul(&NoSimd::new());
+        test_xor_then_mul(&DefaultEngine::new());
+
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            cpufeatures::new!(has_avx2, "avx2");
+            if has_avx2::get() {
+                test_xor_then_mul(&Avx2::new());
+            }
+
+            cpufeatures::new!(has_ssse3, "ssse3");
+            if has_ssse3::get() {
+                test_xor_then_mul(&Ssse3::new());
+            }
+        }
+    }
+}
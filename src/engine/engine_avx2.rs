@@ -1,4 +1,4 @@
-use core::iter::zip;
+use core::{fmt, iter::zip};
 
 #[cfg(target_arch = "x86")]
 use core::arch::x86::*;
@@ -50,6 +50,10 @@ impl Engine for Avx2 {
         truncated_size: usize,
         skew_delta: usize,
     ) {
+        debug_assert!(size.is_power_of_two());
+        debug_assert!(truncated_size <= size);
+        debug_assert!(pos + size <= data.len());
+
         unsafe {
             self.fft_private_avx2(data, pos, size, truncated_size, skew_delta);
         }
@@ -63,6 +67,10 @@ impl Engine for Avx2 {
         truncated_size: usize,
         skew_delta: usize,
     ) {
+        debug_assert!(size.is_power_of_two());
+        debug_assert!(truncated_size <= size);
+        debug_assert!(pos + size <= data.len());
+
         unsafe {
             self.ifft_private_avx2(data, pos, size, truncated_size, skew_delta);
         }
@@ -77,6 +85,32 @@ impl Engine for Avx2 {
     fn eval_poly(erasures: &mut [GfElement; GF_ORDER], truncated_size: usize) {
         unsafe { Self::eval_poly_avx2(erasures, truncated_size) }
     }
+
+    fn add_xor(&self, dst: &mut [[u8; 64]], src: &[[u8; 64]]) {
+        unsafe {
+            Self::add_xor_avx2(dst, src);
+        }
+    }
+
+    fn is_zero_shard(&self, x: &[[u8; 64]]) -> bool {
+        unsafe { Self::is_zero_shard_avx2(x) }
+    }
+
+    fn xor_then_mul(&self, x: &mut [[u8; 64]], y: &[[u8; 64]], log_m: GfElement) {
+        unsafe {
+            self.xor_then_mul_avx2(x, y, log_m);
+        }
+    }
+}
+
+// ======================================================================
+// Avx2 - IMPL Debug
+
+// Tables are not shown, they are the same for every instance.
+impl fmt::Debug for Avx2 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Avx2").finish_non_exhaustive()
+    }
 }
 
 // ======================================================================
@@ -140,6 +174,36 @@ impl From<&Multiply128lutT> for LutAvx2 {
 }
 
 impl Avx2 {
+    #[target_feature(enable = "avx2")]
+    unsafe fn add_xor_avx2(dst: &mut [[u8; 64]], src: &[[u8; 64]]) {
+        debug_assert_eq!(dst.len(), src.len());
+
+        for (dst_chunk, src_chunk) in zip(dst.iter_mut(), src.iter()) {
+            let dst_ptr = dst_chunk.as_mut_ptr().cast::<__m256i>();
+            let src_ptr = src_chunk.as_ptr().cast::<__m256i>();
+            unsafe {
+                let dst_lo = _mm256_loadu_si256(dst_ptr);
+                let dst_hi = _mm256_loadu_si256(dst_ptr.add(1));
+                let src_lo = _mm256_loadu_si256(src_ptr);
+                let src_hi = _mm256_loadu_si256(src_ptr.add(1));
+                _mm256_storeu_si256(dst_ptr, _mm256_xor_si256(dst_lo, src_lo));
+                _mm256_storeu_si256(dst_ptr.add(1), _mm256_xor_si256(dst_hi, src_hi));
+            }
+        }
+    }
+
+    #[target_feature(enable = "avx2")]
+    unsafe fn is_zero_shard_avx2(x: &[[u8; 64]]) -> bool {
+        for chunk in x {
+            let (lo, hi) = Self::load_256(chunk);
+            let or = _mm256_or_si256(lo, hi);
+            if _mm256_testz_si256(or, or) == 0 {
+                return false;
+            }
+        }
+        true
+    }
+
     #[target_feature(enable = "avx2")]
     unsafe fn mul_avx2(&self, x: &mut [[u8; 64]], log_m: GfElement) {
         let lut = &self.mul128[log_m as usize];
@@ -157,6 +221,28 @@ impl Avx2 {
         }
     }
 
+    #[target_feature(enable = "avx2")]
+    unsafe fn xor_then_mul_avx2(&self, x: &mut [[u8; 64]], y: &[[u8; 64]], log_m: GfElement) {
+        debug_assert_eq!(x.len(), y.len());
+
+        let lut = &self.mul128[log_m as usize];
+        let lut_avx2 = LutAvx2::from(lut);
+
+        for (x_chunk, y_chunk) in zip(x.iter_mut(), y.iter()) {
+            let x_ptr = x_chunk.as_mut_ptr().cast::<__m256i>();
+            let y_ptr = y_chunk.as_ptr().cast::<__m256i>();
+            unsafe {
+                let x_lo = _mm256_loadu_si256(x_ptr);
+                let x_hi = _mm256_loadu_si256(x_ptr.add(1));
+                let y_lo = _mm256_loadu_si256(y_ptr);
+                let y_hi = _mm256_loadu_si256(y_ptr.add(1));
+                let (x_lo, x_hi) = Self::muladd_256(x_lo, x_hi, y_lo, y_hi, lut_avx2);
+                _mm256_storeu_si256(x_ptr, x_lo);
+                _mm256_storeu_si256(x_ptr.add(1), x_hi);
+            }
+        }
+    }
+
     // Impelemntation of LEO_MUL_256
     #[inline(always)]
     fn mul_256(value_lo: __m256i, value_hi: __m256i, lut_avx2: LutAvx2) -> (__m256i, __m256i) {
@@ -203,6 +289,28 @@ impl Avx2 {
         }
         (x_lo, x_hi)
     }
+
+    // Loads 64-byte chunk as `(lo, hi)`.
+    #[inline(always)]
+    fn load_256(x: &[u8; 64]) -> (__m256i, __m256i) {
+        let x_ptr = x.as_ptr().cast::<__m256i>();
+        unsafe { (_mm256_loadu_si256(x_ptr), _mm256_loadu_si256(x_ptr.add(1))) }
+    }
+
+    // Stores `(lo, hi)` into 64-byte chunk.
+    #[inline(always)]
+    fn store_256(x: &mut [u8; 64], value: (__m256i, __m256i)) {
+        let x_ptr = x.as_mut_ptr().cast::<__m256i>();
+        unsafe {
+            _mm256_storeu_si256(x_ptr, value.0);
+            _mm256_storeu_si256(x_ptr.add(1), value.1);
+        }
+    }
+
+    #[inline(always)]
+    fn xor_256(x: (__m256i, __m256i), y: (__m256i, __m256i)) -> (__m256i, __m256i) {
+        unsafe { (_mm256_xor_si256(x.0, y.0), _mm256_xor_si256(x.1, y.1)) }
+    }
 }
 
 // ======================================================================
@@ -212,27 +320,21 @@ impl Avx2 {
     // Implementation of LEO_FFTB_256
     #[inline(always)]
     fn fftb_256(x: &mut [u8; 64], y: &mut [u8; 64], lut_avx2: LutAvx2) {
-        let x_ptr = x.as_mut_ptr().cast::<__m256i>();
-        let y_ptr = y.as_mut_ptr().cast::<__m256i>();
-
-        unsafe {
-            let mut x_lo = _mm256_loadu_si256(x_ptr);
-            let mut x_hi = _mm256_loadu_si256(x_ptr.add(1));
-
-            let mut y_lo = _mm256_loadu_si256(y_ptr);
-            let mut y_hi = _mm256_loadu_si256(y_ptr.add(1));
-
-            (x_lo, x_hi) = Self::muladd_256(x_lo, x_hi, y_lo, y_hi, lut_avx2);
-
-            _mm256_storeu_si256(x_ptr, x_lo);
-            _mm256_storeu_si256(x_ptr.add(1), x_hi);
-
-            y_lo = _mm256_xor_si256(y_lo, x_lo);
-            y_hi = _mm256_xor_si256(y_hi, x_hi);
+        let (x_256, y_256) = Self::fftb_256_reg(Self::load_256(x), Self::load_256(y), lut_avx2);
+        Self::store_256(x, x_256);
+        Self::store_256(y, y_256);
+    }
 
-            _mm256_storeu_si256(y_ptr, y_lo);
-            _mm256_storeu_si256(y_ptr.add(1), y_hi);
-        }
+    // Like `fftb_256` but with chunks already loaded into registers.
+    #[inline(always)]
+    fn fftb_256_reg(
+        x: (__m256i, __m256i),
+        y: (__m256i, __m256i),
+        lut_avx2: LutAvx2,
+    ) -> ((__m256i, __m256i), (__m256i, __m256i)) {
+        let x = Self::muladd_256(x.0, x.1, y.0, y.1, lut_avx2);
+        let y = Self::xor_256(y, x);
+        (x, y)
     }
 
     // Partial butterfly, caller must do `GF_MODULUS` check with `xor`.
@@ -256,7 +358,13 @@ impl Avx2 {
         log_m23: GfElement,
         log_m02: GfElement,
     ) {
-        let (s0, s1, s2, s3) = data.dist4_mut(pos, dist);
+        let (s0, s1, s2, s3) = match data.dist4_mut(pos, dist) {
+            ([s0], [s1], [s2], [s3]) => {
+                self.fft_butterfly_two_layers_64([s0, s1, s2, s3], log_m01, log_m23, log_m02);
+                return;
+            }
+            (s0, s1, s2, s3) => (s0, s1, s2, s3),
+        };
 
         // FIRST LAYER
 
@@ -283,6 +391,57 @@ impl Avx2 {
         }
     }
 
+    // Like `fft_butterfly_two_layers` but for shards of a single 64-byte chunk.
+    // All four shards stay in registers over both layers
+    // so each is loaded and stored only once.
+    #[inline(always)]
+    fn fft_butterfly_two_layers_64(
+        &self,
+        s: [&mut [u8; 64]; 4],
+        log_m01: GfElement,
+        log_m23: GfElement,
+        log_m02: GfElement,
+    ) {
+        let [s0, s1, s2, s3] = s;
+
+        let mut v0 = Self::load_256(s0);
+        let mut v1 = Self::load_256(s1);
+        let mut v2 = Self::load_256(s2);
+        let mut v3 = Self::load_256(s3);
+
+        // FIRST LAYER
+
+        if log_m02 == GF_MODULUS {
+            v2 = Self::xor_256(v2, v0);
+            v3 = Self::xor_256(v3, v1);
+        } else {
+            let lut_avx2 = LutAvx2::from(&self.mul128[log_m02 as usize]);
+            (v0, v2) = Self::fftb_256_reg(v0, v2, lut_avx2);
+            (v1, v3) = Self::fftb_256_reg(v1, v3, lut_avx2);
+        }
+
+        // SECOND LAYER
+
+        if log_m01 == GF_MODULUS {
+            v1 = Self::xor_256(v1, v0);
+        } else {
+            let lut_avx2 = LutAvx2::from(&self.mul128[log_m01 as usize]);
+            (v0, v1) = Self::fftb_256_reg(v0, v1, lut_avx2);
+        }
+
+        if log_m23 == GF_MODULUS {
+            v3 = Self::xor_256(v3, v2);
+        } else {
+            let lut_avx2 = LutAvx2::from(&self.mul128[log_m23 as usize]);
+            (v2, v3) = Self::fftb_256_reg(v2, v3, lut_avx2);
+        }
+
+        Self::store_256(s0, v0);
+        Self::store_256(s1, v1);
+        Self::store_256(s2, v2);
+        Self::store_256(s3, v3);
+    }
+
     #[target_feature(enable = "avx2")]
     unsafe fn fft_private_avx2(
         &self,
@@ -1,3 +1,5 @@
+use core::fmt;
+
 use crate::engine::{Engine, GfElement, NoSimd, ShardsRefMut, GF_ORDER};
 #[cfg(not(feature = "std"))]
 use alloc::boxed::Box;
@@ -25,17 +27,31 @@ impl DefaultEngine {
     /// On `AArch64` the engine is chosen in the following order of preference:
     /// 1. [`Neon`]
     /// 2. [`NoSimd`]
+    ///
+    /// With debug assertions enabled this also checks
+    /// [`Engine::verify_tables`] of the chosen engine.
     pub fn new() -> Self {
+        let engine = Self(Self::select());
+        debug_assert!(engine.verify_tables());
+        engine
+    }
+}
+
+// ======================================================================
+// DefaultEngine - PRIVATE
+
+impl DefaultEngine {
+    fn select() -> Box<dyn Engine + Send + Sync> {
         #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
         {
             cpufeatures::new!(has_avx2, "avx2");
             if has_avx2::get() {
-                return Self(Box::new(Avx2::new()));
+                return Box::new(Avx2::new());
             }
 
             cpufeatures::new!(has_ssse3, "ssse3");
             if has_ssse3::get() {
-                return Self(Box::new(Ssse3::new()));
+                return Box::new(Ssse3::new());
             }
         }
 
@@ -43,11 +59,21 @@ impl DefaultEngine {
         {
             cpufeatures::new!(has_neon, "neon");
             if has_neon::get() {
-                return Self(Box::new(Neon::new()));
+                return Box::new(Neon::new());
             }
         }
 
-        Self(Box::new(NoSimd::new()))
+        Box::new(NoSimd::new())
+    }
+}
+
+// ======================================================================
+// DefaultEngine - IMPL Debug
+
+// Shows the selected engine.
+impl fmt::Debug for DefaultEngine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("DefaultEngine").field(&self.0).finish()
     }
 }
 
@@ -114,4 +140,47 @@ impl Engine for DefaultEngine {
 
         NoSimd::eval_poly(erasures, truncated_size);
     }
+
+    fn add_xor(&self, dst: &mut [[u8; 64]], src: &[[u8; 64]]) {
+        self.0.add_xor(dst, src);
+    }
+
+    fn fft_butterfly_one_layer(&self, shards: &mut ShardsRefMut, dist: usize, log_m: GfElement) {
+        self.0.fft_butterfly_one_layer(shards, dist, log_m);
+    }
+
+    fn fft_with_skew(
+        &self,
+        data: &mut ShardsRefMut,
+        pos: usize,
+        size: usize,
+        truncated_size: usize,
+        skew: &[GfElement],
+    ) {
+        self.0.fft_with_skew(data, pos, size, truncated_size, skew);
+    }
+
+    fn gf_polynomial(&self) -> u32 {
+        self.0.gf_polynomial()
+    }
+
+    fn is_zero_shard(&self, x: &[[u8; 64]]) -> bool {
+        self.0.is_zero_shard(x)
+    }
+
+    fn mul_inverse_shard(&self, x: &mut [[u8; 64]]) {
+        self.0.mul_inverse_shard(x);
+    }
+
+    fn preferred_shard_len_multiple(&self) -> usize {
+        self.0.preferred_shard_len_multiple()
+    }
+
+    fn verify_tables(&self) -> bool {
+        self.0.verify_tables()
+    }
+
+    fn xor_then_mul(&self, x: &mut [[u8; 64]], y: &[[u8; 64]], log_m: GfElement) {
+        self.0.xor_then_mul(x, y, log_m);
+    }
 }
@@ -1,3 +1,5 @@
+use core::fmt;
+
 use crate::engine::{
     tables::{self, Exp, Log, Skew},
     utils, Engine, GfElement, ShardsRefMut, GF_MODULUS,
@@ -12,6 +14,9 @@ use crate::engine::{
 ///   the source code to understand [`Engine`].
 /// - [`Naive`] also includes some debug assertions
 ///   which are not present in other implementations.
+/// - [`Naive`] writes out FFT and IFFT butterflies directly instead of
+///   using shared helpers of other engines, so it serves as an
+///   independent reference which other engines are tested against.
 #[derive(Clone, Copy)]
 pub struct Naive {
     exp: &'static Exp,
@@ -50,26 +55,9 @@ impl Engine for Naive {
     ) {
         debug_assert!(size.is_power_of_two());
         debug_assert!(truncated_size <= size);
+        debug_assert!(pos + size <= data.len());
 
-        let mut dist = size / 2;
-        while dist > 0 {
-            let mut r = 0;
-            while r < truncated_size {
-                let log_m = self.skew[r + dist + skew_delta - 1];
-                for i in r..r + dist {
-                    let (a, b) = data.dist2_mut(pos + i, dist);
-
-                    // FFT BUTTERFLY
-
-                    if log_m != GF_MODULUS {
-                        self.mul_add(a, b, log_m);
-                    }
-                    utils::xor(b, a);
-                }
-                r += dist * 2;
-            }
-            dist /= 2;
-        }
+        self.fft_private(data, pos, size, truncated_size, self.skew, skew_delta);
     }
 
     fn ifft(
@@ -82,6 +70,7 @@ impl Engine for Naive {
     ) {
         debug_assert!(size.is_power_of_two());
         debug_assert!(truncated_size <= size);
+        debug_assert!(pos + size <= data.len());
 
         let mut dist = 1;
         while dist < size {
@@ -115,6 +104,32 @@ impl Engine for Naive {
             }
         }
     }
+
+    fn fft_with_skew(
+        &self,
+        data: &mut ShardsRefMut,
+        pos: usize,
+        size: usize,
+        truncated_size: usize,
+        skew: &[GfElement],
+    ) {
+        debug_assert!(size.is_power_of_two());
+        debug_assert!(truncated_size <= size);
+        debug_assert!(pos + size <= data.len());
+        assert!(skew.len() + 1 >= size);
+
+        self.fft_private(data, pos, size, truncated_size, skew, 0);
+    }
+}
+
+// ======================================================================
+// Naive - IMPL Debug
+
+// Tables are not shown, they are the same for every instance.
+impl fmt::Debug for Naive {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Naive").finish_non_exhaustive()
+    }
 }
 
 // ======================================================================
@@ -130,6 +145,36 @@ impl Default for Naive {
 // Naive - PRIVATE
 
 impl Naive {
+    fn fft_private(
+        &self,
+        data: &mut ShardsRefMut,
+        pos: usize,
+        size: usize,
+        truncated_size: usize,
+        skew: &[GfElement],
+        skew_delta: usize,
+    ) {
+        let mut dist = size / 2;
+        while dist > 0 {
+            let mut r = 0;
+            while r < truncated_size {
+                let log_m = skew[r + dist + skew_delta - 1];
+                for i in r..r + dist {
+                    let (a, b) = data.dist2_mut(pos + i, dist);
+
+                    // FFT BUTTERFLY
+
+                    if log_m != GF_MODULUS {
+                        self.mul_add(a, b, log_m);
+                    }
+                    utils::xor(b, a);
+                }
+                r += dist * 2;
+            }
+            dist /= 2;
+        }
+    }
+
     /// `x[] ^= y[] * log_m`
     fn mul_add(&self, x: &mut [[u8; 64]], y: &[[u8; 64]], log_m: GfElement) {
         debug_assert_eq!(x.len(), y.len());
@@ -3,7 +3,7 @@ use crate::engine::{
     utils, Engine, GfElement, ShardsRefMut, GF_MODULUS, GF_ORDER,
 };
 use core::arch::aarch64::*;
-use core::iter::zip;
+use core::{fmt, iter::zip};
 
 // ======================================================================
 // Neon - PUBLIC
@@ -45,6 +45,10 @@ impl Engine for Neon {
         truncated_size: usize,
         skew_delta: usize,
     ) {
+        debug_assert!(size.is_power_of_two());
+        debug_assert!(truncated_size <= size);
+        debug_assert!(pos + size <= data.len());
+
         unsafe {
             self.fft_private_neon(data, pos, size, truncated_size, skew_delta);
         }
@@ -58,6 +62,10 @@ impl Engine for Neon {
         truncated_size: usize,
         skew_delta: usize,
     ) {
+        debug_assert!(size.is_power_of_two());
+        debug_assert!(truncated_size <= size);
+        debug_assert!(pos + size <= data.len());
+
         unsafe {
             self.ifft_private_neon(data, pos, size, truncated_size, skew_delta);
         }
@@ -72,6 +80,26 @@ impl Engine for Neon {
     fn eval_poly(erasures: &mut [GfElement; GF_ORDER], truncated_size: usize) {
         unsafe { Self::eval_poly_neon(erasures, truncated_size) }
     }
+
+    fn is_zero_shard(&self, x: &[[u8; 64]]) -> bool {
+        unsafe { Self::is_zero_shard_neon(x) }
+    }
+
+    fn add_xor(&self, dst: &mut [[u8; 64]], src: &[[u8; 64]]) {
+        unsafe {
+            Self::add_xor_neon(dst, src);
+        }
+    }
+}
+
+// ======================================================================
+// Neon - IMPL Debug
+
+// Tables are not shown, they are the same for every instance.
+impl fmt::Debug for Neon {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Neon").finish_non_exhaustive()
+    }
 }
 
 // ======================================================================
@@ -89,6 +117,38 @@ impl Default for Neon {
 //
 
 impl Neon {
+    #[target_feature(enable = "neon")]
+    unsafe fn add_xor_neon(dst: &mut [[u8; 64]], src: &[[u8; 64]]) {
+        debug_assert_eq!(dst.len(), src.len());
+
+        for (dst_chunk, src_chunk) in zip(dst.iter_mut(), src.iter()) {
+            let dst_ptr: *mut u8 = dst_chunk.as_mut_ptr();
+            let src_ptr: *const u8 = src_chunk.as_ptr();
+            for i in 0..4 {
+                unsafe {
+                    let dst_i = vld1q_u8(dst_ptr.add(16 * i));
+                    let src_i = vld1q_u8(src_ptr.add(16 * i));
+                    vst1q_u8(dst_ptr.add(16 * i), veorq_u8(dst_i, src_i));
+                }
+            }
+        }
+    }
+
+    #[target_feature(enable = "neon")]
+    unsafe fn is_zero_shard_neon(x: &[[u8; 64]]) -> bool {
+        for chunk in x {
+            let x_ptr: *const u8 = chunk.as_ptr();
+            unsafe {
+                let or01 = vorrq_u8(vld1q_u8(x_ptr), vld1q_u8(x_ptr.add(16)));
+                let or23 = vorrq_u8(vld1q_u8(x_ptr.add(16 * 2)), vld1q_u8(x_ptr.add(16 * 3)));
+                if vmaxvq_u8(vorrq_u8(or01, or23)) != 0 {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
     #[target_feature(enable = "neon")]
     unsafe fn mul_neon(&self, x: &mut [[u8; 64]], log_m: GfElement) {
         let lut = &self.mul128[log_m as usize];
@@ -223,6 +283,9 @@ impl Neon {
         }
     }
 
+    // Unlike `Avx2` this has no variant keeping 64-byte shards in registers
+    // over both layers. Neon has enough registers for one,
+    // but it hasn't been benchmarked on `AArch64` hardware yet.
     #[inline(always)]
     fn fft_butterfly_two_layers(
         &self,
@@ -1,8 +1,8 @@
-use core::iter::zip;
+use core::{fmt, iter::zip};
 
 use crate::engine::{
-    tables::{self, Mul16, Skew},
-    utils, Engine, GfElement, ShardsRefMut, GF_MODULUS,
+    tables::{self, FieldTables, Mul16, Skew},
+    utils, Engine, GfElement, GfField, ShardsRefMut, GF_MODULUS, GF_ORDER, GF_POLYNOMIAL,
 };
 
 // ======================================================================
@@ -15,6 +15,7 @@ use crate::engine::{
 pub struct NoSimd {
     mul16: &'static Mul16,
     skew: &'static Skew,
+    field_tables: Option<&'static FieldTables>,
 }
 
 impl NoSimd {
@@ -29,7 +30,64 @@ impl NoSimd {
         let mul16 = tables::get_mul16();
         let skew = tables::get_skew();
 
-        Self { mul16, skew }
+        Self {
+            mul16,
+            skew,
+            field_tables: None,
+        }
+    }
+
+    /// Creates new [`NoSimd`] using given [`GfField`],
+    /// initializing all [tables] needed for encoding.
+    ///
+    /// With [`GfField::DEFAULT`] this is same as [`NoSimd::new`].
+    /// Otherwise [`FieldTables`] (about 8.5 MiB) of the field are generated
+    /// on first call and kept for the lifetime of the program,
+    /// later calls with the same field reuse them,
+    /// see [`tables::get_field_tables`].
+    ///
+    /// Shards encoded using a custom field can only be decoded
+    /// using the same field.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simd_erasure_core::{
+    ///     engine::NoSimd,
+    ///     rate::{RateDecoder, RateEncoder},
+    ///     DefaultRateDecoder, DefaultRateEncoder, GfField, GfPolynomial,
+    /// };
+    ///
+    /// let field = GfField::new(GfPolynomial(0x100B), 2).unwrap();
+    /// let engine = NoSimd::new_with_field(field);
+    ///
+    /// let mut encoder = DefaultRateEncoder::new(2, 1, 64, engine, None)?;
+    /// encoder.add_original_shard([1; 64])?;
+    /// encoder.add_original_shard([2; 64])?;
+    /// let recovery: Vec<_> = encoder.encode()?.recovery_iter().map(|s| s.to_vec()).collect();
+    ///
+    /// let mut decoder = DefaultRateDecoder::new(2, 1, 64, engine, None)?;
+    /// decoder.add_original_shard(1, [2; 64])?;
+    /// decoder.add_recovery_shard(0, &recovery[0])?;
+    /// let result = decoder.decode()?;
+    ///
+    /// assert_eq!(result.restored_original(0), Some([1; 64].as_slice()));
+    /// # Ok::<(), simd_erasure_core::Error>(())
+    /// ```
+    ///
+    /// [tables]: crate::engine::tables
+    pub fn new_with_field(field: GfField) -> Self {
+        if field == GfField::DEFAULT {
+            return Self::new();
+        }
+
+        let field_tables = tables::get_field_tables(field);
+
+        Self {
+            mul16: field_tables.mul16(),
+            skew: field_tables.skew(),
+            field_tables: Some(field_tables),
+        }
     }
 }
 
@@ -42,7 +100,11 @@ impl Engine for NoSimd {
         truncated_size: usize,
         skew_delta: usize,
     ) {
-        self.fft_private(data, pos, size, truncated_size, skew_delta);
+        debug_assert!(size.is_power_of_two());
+        debug_assert!(truncated_size <= size);
+        debug_assert!(pos + size <= data.len());
+
+        self.fft_private(data, pos, size, truncated_size, self.skew, skew_delta);
     }
 
     fn ifft(
@@ -53,6 +115,10 @@ impl Engine for NoSimd {
         truncated_size: usize,
         skew_delta: usize,
     ) {
+        debug_assert!(size.is_power_of_two());
+        debug_assert!(truncated_size <= size);
+        debug_assert!(pos + size <= data.len());
+
         self.ifft_private(data, pos, size, truncated_size, skew_delta);
     }
 
@@ -74,6 +140,61 @@ impl Engine for NoSimd {
             }
         }
     }
+
+    fn eval_poly_in_field(&self, erasures: &mut [GfElement; GF_ORDER], truncated_size: usize) {
+        match self.field_tables {
+            Some(field_tables) => {
+                utils::eval_poly_with_log_walsh(erasures, truncated_size, field_tables.log_walsh());
+            }
+            None => Self::eval_poly(erasures, truncated_size),
+        }
+    }
+
+    fn fft_with_skew(
+        &self,
+        data: &mut ShardsRefMut,
+        pos: usize,
+        size: usize,
+        truncated_size: usize,
+        skew: &[GfElement],
+    ) {
+        debug_assert!(size.is_power_of_two());
+        debug_assert!(truncated_size <= size);
+        debug_assert!(pos + size <= data.len());
+        assert!(skew.len() + 1 >= size);
+
+        self.fft_private(data, pos, size, truncated_size, skew, 0);
+    }
+
+    fn gf_polynomial(&self) -> u32 {
+        match self.field_tables {
+            Some(field_tables) => 0x10000 | u32::from(field_tables.field().poly().0),
+            None => GF_POLYNOMIAL as u32,
+        }
+    }
+
+    fn mul_inverse_shard(&self, x: &mut [[u8; 64]]) {
+        match self.field_tables {
+            Some(field_tables) => utils::mul_inverse_shard_with_exp_log(x, field_tables.exp_log()),
+            None => utils::mul_inverse_shard_with_exp_log(x, tables::get_exp_log()),
+        }
+    }
+}
+
+// ======================================================================
+// NoSimd - IMPL Debug
+
+// Tables are not shown, only the field they were generated for.
+impl fmt::Debug for NoSimd {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let field = self
+            .field_tables
+            .map_or(GfField::DEFAULT, FieldTables::field);
+
+        f.debug_struct("NoSimd")
+            .field("field", &field)
+            .finish_non_exhaustive()
+    }
 }
 
 // ======================================================================
@@ -166,6 +287,7 @@ impl NoSimd {
         pos: usize,
         size: usize,
         truncated_size: usize,
+        skew: &[GfElement],
         skew_delta: usize,
     ) {
         // TWO LAYERS AT TIME
@@ -177,9 +299,9 @@ impl NoSimd {
             while r < truncated_size {
                 let base = r + dist + skew_delta - 1;
 
-                let log_m01 = self.skew[base];
-                let log_m02 = self.skew[base + dist];
-                let log_m23 = self.skew[base + dist * 2];
+                let log_m01 = skew[base];
+                let log_m02 = skew[base + dist];
+                let log_m23 = skew[base + dist * 2];
 
                 for i in r..r + dist {
                     self.fft_butterfly_two_layers(data, pos + i, dist, log_m01, log_m23, log_m02);
@@ -196,7 +318,7 @@ impl NoSimd {
         if dist4 == 2 {
             let mut r = 0;
             while r < truncated_size {
-                let log_m = self.skew[r + skew_delta];
+                let log_m = skew[r + skew_delta];
 
                 let (x, y) = data.dist2_mut(pos + r, 1);
 
@@ -0,0 +1,179 @@
+use crate::engine::{GfElement, CANTOR_BASIS, GF_BITS, GF_MODULUS, GF_POLYNOMIAL};
+
+// ======================================================================
+// GfPolynomial - PUBLIC
+
+/// Galois field polynomial of GF(2^16) without the implicit `x^16` term.
+///
+/// E.g. the default polynomial `x^16 + x^5 + x^3 + x^2 + 1`
+/// ([`GF_POLYNOMIAL`] `0x1002D`) is `GfPolynomial(0x002D)`.
+///
+/// [`GF_POLYNOMIAL`]: crate::engine::GF_POLYNOMIAL
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GfPolynomial(pub u16);
+
+// ======================================================================
+// GfField - PUBLIC
+
+/// Galois field GF(2^16) defined by polynomial and generator.
+///
+/// All [`Engine`]:s use [`GfField::DEFAULT`] unless created
+/// with a custom field, e.g. with [`NoSimd::new_with_field`].
+///
+/// Shards encoded using one field can only be decoded using the same field.
+///
+/// # Examples
+///
+/// ```rust
+/// use simd_erasure_core::engine::{GfField, GfPolynomial};
+///
+/// // x^16 + x^12 + x^3 + x + 1 with generator x
+/// assert!(GfField::new(GfPolynomial(0x100B), 2).is_some());
+///
+/// // x^16 + x^5 + x^3 + x + 1 is not primitive
+/// assert!(GfField::new(GfPolynomial(0x002B), 2).is_none());
+/// ```
+///
+/// [`Engine`]: crate::engine::Engine
+/// [`NoSimd::new_with_field`]: crate::engine::NoSimd::new_with_field
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GfField {
+    poly: GfPolynomial,
+    generator: GfElement,
+}
+
+impl GfField {
+    /// Field used by default: polynomial [`GF_POLYNOMIAL`] with generator `2`.
+    ///
+    /// [`GF_POLYNOMIAL`]: crate::engine::GF_POLYNOMIAL
+    pub const DEFAULT: GfField = GfField {
+        poly: GfPolynomial(GF_POLYNOMIAL as u16),
+        generator: 2,
+    };
+
+    /// Returns generator of this field.
+    pub fn generator(&self) -> GfElement {
+        self.generator
+    }
+
+    /// Creates new [`GfField`] or returns `None` if `generator`
+    /// isn't a primitive element of the field defined by `poly`,
+    /// i.e. if powers of `generator` don't cover all non-zero elements.
+    pub fn new(poly: GfPolynomial, generator: GfElement) -> Option<Self> {
+        let field = Self { poly, generator };
+
+        let mut state = generator;
+        for _ in 1..GF_MODULUS {
+            if state == 1 {
+                return None;
+            }
+            state = field.mul(state, generator);
+        }
+
+        if state == 1 {
+            Some(field)
+        } else {
+            None
+        }
+    }
+
+    /// Returns polynomial of this field.
+    pub fn poly(&self) -> GfPolynomial {
+        self.poly
+    }
+}
+
+// ======================================================================
+// GfField - IMPL Default
+
+impl Default for GfField {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+// ======================================================================
+// GfField - CRATE
+
+impl GfField {
+    // Cantor basis of this field in polynomial basis,
+    // i.e. `basis[0] == 1` and `basis[i]^2 + basis[i] == basis[i - 1]`.
+    pub(crate) fn cantor_basis(self) -> [GfElement; GF_BITS] {
+        if self == Self::DEFAULT {
+            return CANTOR_BASIS;
+        }
+
+        let mut basis = [0; GF_BITS];
+        basis[0] = 1;
+
+        // `x^2 + x == c` has two solutions which differ only by lowest bit,
+        // same as with `CANTOR_BASIS` the one with lowest bit clear is chosen.
+        for i in 1..GF_BITS {
+            basis[i] = (0..=GF_MODULUS)
+                .step_by(2)
+                .find(|&x| self.mul(x, x) ^ x == basis[i - 1])
+                .expect("Cantor basis always exists in GF(2^16)");
+        }
+
+        basis
+    }
+
+    // Multiplication in polynomial basis.
+    pub(crate) fn mul(self, a: GfElement, b: GfElement) -> GfElement {
+        let mut product: GfElement = 0;
+        let mut a = a;
+        for i in 0..GF_BITS {
+            if b & (1 << i) != 0 {
+                product ^= a;
+            }
+            let carry = a & 0x8000 != 0;
+            a <<= 1;
+            if carry {
+                a ^= self.poly.0;
+            }
+        }
+        product
+    }
+}
+
+// ======================================================================
+// TESTS
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cantor_basis() {
+        let field = GfField::new(GfPolynomial(0x100B), 2).unwrap();
+        let basis = field.cantor_basis();
+
+        assert_eq!(basis[0], 1);
+        for i in 1..GF_BITS {
+            assert_eq!(field.mul(basis[i], basis[i]) ^ basis[i], basis[i - 1]);
+        }
+    }
+
+    #[test]
+    fn default_cantor_basis_is_computed_cantor_basis() {
+        let field = GfField::DEFAULT;
+        for i in 1..GF_BITS {
+            let b = CANTOR_BASIS[i];
+            assert_eq!(field.mul(b, b) ^ b, CANTOR_BASIS[i - 1]);
+            assert_eq!(b & 1, 0);
+        }
+    }
+
+    #[test]
+    fn new() {
+        assert_eq!(
+            GfField::new(GfPolynomial(GF_POLYNOMIAL as u16), 2),
+            Some(GfField::DEFAULT)
+        );
+        assert!(GfField::new(GfPolynomial(0x100B), 2).is_some());
+
+        assert!(GfField::new(GfPolynomial(0x002B), 2).is_none());
+        assert!(GfField::new(GfPolynomial(0x002D), 0).is_none());
+        assert!(GfField::new(GfPolynomial(0x002D), 1).is_none());
+    }
+}
@@ -1,14 +1,116 @@
 #[cfg(not(feature = "std"))]
-use alloc::vec::Vec;
-use core::ops::{Bound, Index, IndexMut, Range, RangeBounds};
+use alloc::{vec, vec::Vec};
+use core::{
+    fmt, mem,
+    ops::{Bound, Index, IndexMut, Range, RangeBounds},
+};
+
+use crate::{
+    engine::{utils, Engine, GfElement},
+    Error,
+};
+
+// ======================================================================
+// FUNCTIONS - PRIVATE
+
+// Splits `shard_count >= 3` shards into two parts,
+// first of which has even length so that parity of indexes
+// in second part is same as in whole.
+fn interleave_split(shard_count: usize) -> usize {
+    (shard_count / 2 + 1) & !1
+}
+
+// Copies `shard` into `dst` which must have room for it.
+fn insert_shard(dst: &mut [[u8; 64]], shard: &[u8]) {
+    debug_assert_eq!(shard.len() % 2, 0);
+
+    let whole_chunk_count = shard.len() / 64;
+    let tail_len = shard.len() % 64;
+
+    let (src_chunks, src_tail) = shard.split_at(shard.len() - tail_len);
+
+    dst[..whole_chunk_count]
+        .as_flattened_mut()
+        .copy_from_slice(src_chunks);
+
+    // Last chunk is special if shard.len() % 64 != 0.
+    // See src/algorithm.md for an explanation.
+    if tail_len > 0 {
+        let (src_lo, src_hi) = src_tail.split_at(tail_len / 2);
+        let (dst_lo, dst_hi) = dst[whole_chunk_count].split_at_mut(32);
+        dst_lo[..src_lo.len()].copy_from_slice(src_lo);
+        dst_hi[..src_hi.len()].copy_from_slice(src_hi);
+    }
+}
+
+// Inverse of `insert_shard`, copies `shard_bytes` bytes from `src` into new `Vec`.
+fn extract_shard(src: &[[u8; 64]], shard_bytes: usize) -> Vec<u8> {
+    let whole_chunk_count = shard_bytes / 64;
+    let tail_len = shard_bytes % 64;
+
+    let mut shard = Vec::with_capacity(shard_bytes);
+    shard.extend_from_slice(src[..whole_chunk_count].as_flattened());
+
+    if tail_len > 0 {
+        let (src_lo, src_hi) = src[whole_chunk_count].split_at(32);
+        shard.extend_from_slice(&src_lo[..tail_len / 2]);
+        shard.extend_from_slice(&src_hi[..tail_len / 2]);
+    }
+
+    shard
+}
+
+// Inverse of `interleave`, steps are done in reverse order.
+fn deinterleave(data: &mut [[u8; 64]], shard_count: usize, shard_len_64: usize) {
+    if shard_count <= 2 {
+        return;
+    }
+
+    let mid = interleave_split(shard_count);
+    let even_count = shard_count.div_ceil(2);
+
+    // [E_L E_R O_L O_R] -> [E_L O_L E_R O_R]
+    let left_even_count = mid / 2;
+    let right_even_count = even_count - left_even_count;
+    data[left_even_count * shard_len_64..(mid + right_even_count) * shard_len_64]
+        .rotate_left(right_even_count * shard_len_64);
+
+    let (left, right) = data.split_at_mut(mid * shard_len_64);
+    deinterleave(left, mid, shard_len_64);
+    deinterleave(right, shard_count - mid, shard_len_64);
+}
+
+// Stable partition of shards by parity of index:
+// Partitions both halves recursively and then
+// swaps odd shards of first half with even shards of second half.
+fn interleave(data: &mut [[u8; 64]], shard_count: usize, shard_len_64: usize) {
+    if shard_count <= 2 {
+        return;
+    }
+
+    let mid = interleave_split(shard_count);
+
+    let (left, right) = data.split_at_mut(mid * shard_len_64);
+    interleave(left, mid, shard_len_64);
+    interleave(right, shard_count - mid, shard_len_64);
+
+    // [E_L O_L E_R O_R] -> [E_L E_R O_L O_R]
+    let left_even_count = mid / 2;
+    let right_even_count = (shard_count - mid).div_ceil(2);
+    data[left_even_count * shard_len_64..(mid + right_even_count) * shard_len_64]
+        .rotate_left(left_even_count * shard_len_64);
+}
 
 // ======================================================================
 // Shards - CRATE
 
+#[derive(Clone)]
 pub(crate) struct Shards {
     shard_count: usize,
     // Shard length in 64 byte chunks
     shard_len_64: usize,
+    // Shard length in bytes, last chunk of a shard may be partially used.
+    shard_bytes: usize,
 
     // Flat Vec of `shard_count * shard_len_64 * 64` bytes.
     data: Vec<[u8; 64]>,
@@ -23,45 +125,114 @@ impl Shards {
         Self {
             shard_count: 0,
             shard_len_64: 0,
+            shard_bytes: 0,
             data: Vec::new(),
         }
     }
 
-    pub(crate) fn resize(&mut self, shard_count: usize, shard_len_64: usize) {
+    pub(crate) fn resize(&mut self, shard_count: usize, shard_bytes: usize) {
         self.shard_count = shard_count;
-        self.shard_len_64 = shard_len_64;
+        self.shard_len_64 = shard_bytes.div_ceil(64);
+        self.shard_bytes = shard_bytes;
 
         self.data
             .resize(self.shard_count * self.shard_len_64, [0; 64]);
     }
 
-    pub(crate) fn insert(&mut self, index: usize, shard: &[u8]) {
-        debug_assert_eq!(shard.len() % 2, 0);
-
-        let whole_chunk_count = shard.len() / 64;
-        let tail_len = shard.len() % 64;
+    // Like `resize` but keeps existing shard data, like `Vec::resize`.
+    //
+    // First `min(old, new)` chunks of first `min(old, new)` shards are kept,
+    // everything else is zeroed. Not used by encoders/decoders yet
+    // which overwrite all shards after `resize` anyway.
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub(crate) fn resize_preserve(&mut self, shard_count: usize, shard_bytes: usize) {
+        let shard_len_64 = shard_bytes.div_ceil(64);
 
-        let (src_chunks, src_tail) = shard.split_at(shard.len() - tail_len);
+        if shard_len_64 == self.shard_len_64 {
+            self.data.resize(shard_count * shard_len_64, [0; 64]);
+        } else {
+            let mut data = vec![[0; 64]; shard_count * shard_len_64];
+            let keep_len_64 = self.shard_len_64.min(shard_len_64);
 
-        let dst = &mut self[index];
-        dst[..whole_chunk_count]
-            .as_flattened_mut()
-            .copy_from_slice(src_chunks);
+            for index in 0..self.shard_count.min(shard_count) {
+                let src = index * self.shard_len_64;
+                let dst = index * shard_len_64;
+                data[dst..dst + keep_len_64].copy_from_slice(&self.data[src..src + keep_len_64]);
+            }
 
-        // Last chunk is special if shard.len() % 64 != 0.
-        // See src/algorithm.md for an explanation.
-        if tail_len > 0 {
-            let (src_lo, src_hi) = src_tail.split_at(tail_len / 2);
-            let (dst_lo, dst_hi) = dst[whole_chunk_count].split_at_mut(32);
-            dst_lo[..src_lo.len()].copy_from_slice(src_lo);
-            dst_hi[..src_hi.len()].copy_from_slice(src_hi);
+            self.data = data;
         }
+
+        self.shard_count = shard_count;
+        self.shard_len_64 = shard_len_64;
+        self.shard_bytes = shard_bytes;
+    }
+
+    // Shard size in bytes as given to `resize` or `resize_preserve`.
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub(crate) fn shard_bytes_per_shard(&self) -> usize {
+        self.shard_bytes
+    }
+
+    // Returns pointer to start of shard `index` and its length in bytes,
+    // e.g. for setting up DMA descriptors without copying.
+    //
+    // # Safety
+    //
+    // Returned pointer is valid for reading `len` bytes only as long as:
+    // - `resize`, `resize_preserve` or drop hasn't been called,
+    //   since they may reallocate or free the memory.
+    // - No mutable reference to the same shard exists,
+    //   e.g. from `IndexMut` or `as_ref_mut`.
+    //
+    // # Panics
+    //
+    // If `index >= shard_count`.
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub(crate) unsafe fn shard_ptr_range(&self, index: usize) -> (*const u8, usize) {
+        let shard = &self[index];
+        (shard.as_ptr().cast(), shard.len() * 64)
+    }
+
+    // Like `shard_ptr_range` but pointer can be used for writing.
+    //
+    // # Safety
+    //
+    // Returned pointer is valid for reading and writing `len` bytes
+    // only as long as:
+    // - `resize`, `resize_preserve` or drop hasn't been called,
+    //   since they may reallocate or free the memory.
+    // - No other reference to the same shard exists,
+    //   e.g. from `Index` or `as_ref_mut`.
+    //
+    // # Panics
+    //
+    // If `index >= shard_count`.
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub(crate) unsafe fn shard_ptr_range_mut(&mut self, index: usize) -> (*mut u8, usize) {
+        let shard = &mut self[index];
+        (shard.as_mut_ptr().cast(), shard.len() * 64)
+    }
+
+    // Inverse of `insert`, returns copy of shard `index`
+    // which must still have the layout given by `insert`.
+    pub(crate) fn extract(&self, index: usize) -> Vec<u8> {
+        extract_shard(&self[index], self.shard_bytes)
+    }
+
+    pub(crate) fn insert(&mut self, index: usize, shard: &[u8]) {
+        insert_shard(&mut self[index], shard);
     }
 
-    // Undoes the encoding of the last chunk for the given range of shards
-    pub(crate) fn undo_last_chunk_encoding(&mut self, shard_bytes: usize, range: Range<usize>) {
-        let whole_chunk_count = shard_bytes / 64;
-        let tail_len = shard_bytes % 64;
+    // Inverse of the last chunk layout of `insert`, for shards in `range`.
+    //
+    // If `shard_bytes % 64 != 0` the last chunk of a shard holds
+    // first half of the tail at its start and second half at byte 32.
+    // This moves second half right after first half so that the shard
+    // is contiguous in its first `shard_bytes` bytes.
+    pub(crate) fn undo_last_chunk_encoding(&mut self, range: Range<usize>) {
+        let whole_chunk_count = self.shard_bytes / 64;
+        let tail_len = self.shard_bytes % 64;
 
         if tail_len == 0 {
             return;
@@ -72,6 +243,32 @@ impl Shards {
             last_chunk.copy_within(32..32 + tail_len / 2, tail_len / 2);
         }
     }
+
+    // Zeroes shards in `range` (shard indexes, not chunks),
+    // keeping other shards as-is, unlike `resize` which may zero all.
+    //
+    // # Panics
+    //
+    // If `range.end > shard_count`.
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub(crate) fn zero_range(&mut self, range: Range<usize>) {
+        assert!(range.end <= self.shard_count);
+        self.data[range.start * self.shard_len_64..range.end * self.shard_len_64].fill([0; 64]);
+    }
+}
+
+// ======================================================================
+// Shards - IMPL Debug
+
+// Only dimensions are shown, shards may contain user data.
+impl fmt::Debug for Shards {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Shards")
+            .field("shard_count", &self.shard_count)
+            .field("shard_len_64", &self.shard_len_64)
+            .field("data_len", &self.data.len())
+            .finish_non_exhaustive()
+    }
 }
 
 // ======================================================================
@@ -105,6 +302,257 @@ pub struct ShardsRefMut<'a> {
 }
 
 impl<'a> ShardsRefMut<'a> {
+    /// Returns one [`IoSlice`] per shard, each containing
+    /// first `shard_bytes` bytes of that shard.
+    ///
+    /// This allows e.g. [`Write::write_vectored`] directly from shards.
+    ///
+    /// If `shard_bytes` isn't a multiple of 64, last partial 64-byte chunk
+    /// of each shard is in the internal layout described in `src/algorithm.md`
+    /// unless shards contain raw data that hasn't been encoded/decoded.
+    ///
+    /// # Panics
+    ///
+    /// If `shard_bytes` is larger than shard length in bytes.
+    ///
+    /// [`IoSlice`]: std::io::IoSlice
+    /// [`Write::write_vectored`]: std::io::Write::write_vectored
+    #[cfg(feature = "std")]
+    pub fn as_io_slices(&self, shard_bytes: usize) -> Vec<std::io::IoSlice<'_>> {
+        assert!(shard_bytes <= self.shard_len_64 * 64);
+
+        (0..self.shard_count)
+            .map(|index| std::io::IoSlice::new(&self[index].as_flattened()[..shard_bytes]))
+            .collect()
+    }
+
+    /// Returns one [`IoSliceMut`] per shard, each containing
+    /// first `shard_bytes` bytes of that shard.
+    ///
+    /// This allows e.g. [`Read::read_vectored`] directly into shards.
+    ///
+    /// See [`as_io_slices`] for details.
+    ///
+    /// # Panics
+    ///
+    /// If `shard_bytes` is larger than shard length in bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::io::Read;
+    /// use simd_erasure_core::engine::ShardsRefMut;
+    ///
+    /// let mut data = vec![[0u8; 64]; 2];
+    /// let mut shards = ShardsRefMut::new(2, 1, &mut data);
+    ///
+    /// let mut input: &[u8] = &[1, 2, 3, 4, 5, 6];
+    /// let mut slices = shards.as_io_slices_mut(3);
+    /// assert_eq!(input.read_vectored(&mut slices).unwrap(), 6);
+    ///
+    /// assert_eq!(data[0][..3], [1, 2, 3]);
+    /// assert_eq!(data[1][..3], [4, 5, 6]);
+    /// ```
+    ///
+    /// [`IoSliceMut`]: std::io::IoSliceMut
+    /// [`Read::read_vectored`]: std::io::Read::read_vectored
+    /// [`as_io_slices`]: ShardsRefMut::as_io_slices
+    #[cfg(feature = "std")]
+    pub fn as_io_slices_mut(&mut self, shard_bytes: usize) -> Vec<std::io::IoSliceMut<'_>> {
+        assert!(shard_bytes <= self.shard_len_64 * 64);
+
+        let shard_len_64 = self.shard_len_64;
+        let mut rest = &mut self.data[..];
+
+        (0..self.shard_count)
+            .map(|_| {
+                let (shard, tail) = mem::take(&mut rest).split_at_mut(shard_len_64);
+                rest = tail;
+                std::io::IoSliceMut::new(&mut shard.as_flattened_mut()[..shard_bytes])
+            })
+            .collect()
+    }
+
+    /// Returns iterator over non-overlapping pairs of consecutive shards,
+    /// i.e. `(shard 0, shard 1)`, `(shard 2, shard 3)` and so on.
+    ///
+    /// This is useful for butterflies which operate on shard pairs,
+    /// without calling [`dist2_mut`] for each pair.
+    ///
+    /// # Panics
+    ///
+    /// If [`len()`] is odd.
+    ///
+    /// [`dist2_mut`]: ShardsRefMut::dist2_mut
+    /// [`len()`]: ShardsRefMut::len
+    pub fn as_shard_pairs_mut(
+        &mut self,
+    ) -> impl Iterator<Item = (&mut [[u8; 64]], &mut [[u8; 64]])> + '_ {
+        assert!(self.shard_count % 2 == 0);
+
+        let shard_len_64 = self.shard_len_64;
+        let mut rest = &mut self.data[..self.shard_count * shard_len_64];
+
+        (0..self.shard_count / 2).map(move |_| {
+            let (pair, tail) = mem::take(&mut rest).split_at_mut(shard_len_64 * 2);
+            rest = tail;
+            pair.split_at_mut(shard_len_64)
+        })
+    }
+
+    /// XORs shards in given shard-range `sources` into shard `dest`,
+    /// i.e. `dest ^= sources[0] ^ sources[1] ^ ...`.
+    ///
+    /// This is useful e.g. for computing parity shards of outer codes.
+    ///
+    /// # Panics
+    ///
+    /// If `sources.end > len()`, `dest >= len()`
+    /// or if `dest` is in `sources`.
+    pub fn bulk_xor_into(&mut self, sources: Range<usize>, dest: usize) {
+        assert!(sources.end <= self.shard_count);
+        assert!(dest < self.shard_count);
+        assert!(!sources.contains(&dest));
+
+        for src in sources {
+            utils::xor_within(self, dest, src, 1);
+        }
+    }
+
+    /// Copies `slices` into the first `slices.len()` shards,
+    /// like [`ReedSolomonEncoder::add_original_shard`] does
+    /// for each shard.
+    ///
+    /// Shards are stored in the same layout as encoders and decoders use,
+    /// where the last 64-byte chunk of a shard is split in two halves
+    /// if `shard_bytes` isn't a multiple of 64.
+    /// Bytes after `shard_bytes` are left unchanged.
+    ///
+    /// Everything is validated before anything is copied,
+    /// so shards are unchanged if an error is returned.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::TooManyOriginalShards`] if `slices.len() > len()`,
+    ///   with `original_count` being [`len()`].
+    /// - [`Error::InvalidShardSize`] if `shard_bytes` is `0`, odd
+    ///   or larger than shard length.
+    /// - [`Error::DifferentShardSize`] if a slice doesn't have `shard_bytes` bytes.
+    ///
+    /// [`len()`]: ShardsRefMut::len
+    /// [`ReedSolomonEncoder::add_original_shard`]: crate::ReedSolomonEncoder::add_original_shard
+    pub fn copy_from_slice_of_slices<T: AsRef<[u8]>>(
+        &mut self,
+        slices: &[T],
+        shard_bytes: usize,
+    ) -> Result<(), Error> {
+        if slices.len() > self.shard_count {
+            return Err(Error::TooManyOriginalShards {
+                original_count: self.shard_count,
+            });
+        }
+
+        if shard_bytes == 0 || shard_bytes % 2 != 0 || shard_bytes > self.shard_len_64 * 64 {
+            return Err(Error::InvalidShardSize { shard_bytes });
+        }
+
+        if let Some(slice) = slices
+            .iter()
+            .find(|slice| slice.as_ref().len() != shard_bytes)
+        {
+            return Err(Error::DifferentShardSize {
+                shard_bytes,
+                got: slice.as_ref().len(),
+            });
+        }
+
+        for (index, slice) in slices.iter().enumerate() {
+            insert_shard(&mut self[index], slice.as_ref());
+        }
+
+        Ok(())
+    }
+
+    /// Returns iterator over non-overlapping sub-views
+    /// of `chunk_size` shards each.
+    ///
+    /// If [`len()`] isn't a multiple of `chunk_size`,
+    /// the last sub-view contains the remaining shards.
+    ///
+    /// This is like [`slice::chunks_mut`] but for whole shards.
+    ///
+    /// # Panics
+    ///
+    /// If `chunk_size` is `0`.
+    ///
+    /// [`len()`]: ShardsRefMut::len
+    pub fn chunks(&mut self, chunk_size: usize) -> ShardsChunks<'_> {
+        assert!(chunk_size != 0);
+        ShardsChunks::new(chunk_size, self.shard_count, self.shard_len_64, self.data)
+    }
+
+    /// Returns value whose [`Debug`] output contains
+    /// all shards formatted as with [`debug_shard`], keyed by shard index.
+    ///
+    /// This is only available with `debug-output` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "debug-output")]
+    /// # {
+    /// use simd_erasure_core::engine::ShardsRefMut;
+    ///
+    /// let mut data = vec![[0x01; 64], [0xab; 64]];
+    /// let shards = ShardsRefMut::new(2, 1, &mut data);
+    ///
+    /// assert_eq!(
+    ///     format!("{:?}", shards.debug_all_shards()),
+    ///     format!("{{0: {}.., 1: {}..}}", "01".repeat(32), "ab".repeat(32)),
+    /// );
+    /// # }
+    /// ```
+    ///
+    /// [`Debug`]: core::fmt::Debug
+    /// [`debug_shard`]: ShardsRefMut::debug_shard
+    #[cfg(feature = "debug-output")]
+    pub fn debug_all_shards(&self) -> impl fmt::Debug + '_ {
+        DebugAllShards(self)
+    }
+
+    /// Returns value whose [`Debug`] output contains
+    /// first 32 bytes of shard `index` in hex,
+    /// followed by `..` if shard is longer than that.
+    ///
+    /// This is only available with `debug-output` feature.
+    ///
+    /// # Panics
+    ///
+    /// If `index >= len()`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "debug-output")]
+    /// # {
+    /// use simd_erasure_core::engine::ShardsRefMut;
+    ///
+    /// let mut data = vec![[0x00; 64], [0xab; 64]];
+    /// let shards = ShardsRefMut::new(2, 1, &mut data);
+    ///
+    /// assert_eq!(
+    ///     format!("{:?}", shards.debug_shard(1)),
+    ///     "abababababababababababababababababababababababababababababababab..",
+    /// );
+    /// # }
+    /// ```
+    ///
+    /// [`Debug`]: core::fmt::Debug
+    #[cfg(feature = "debug-output")]
+    pub fn debug_shard(&self, index: usize) -> impl fmt::Debug + '_ {
+        DebugShard::<32>(&self[index])
+    }
+
     /// Returns mutable references to shards at `pos` and `pos + dist`.
     ///
     /// See source code of [`Naive::fft`] for an example.
@@ -163,16 +611,154 @@ impl<'a> ShardsRefMut<'a> {
         )
     }
 
+    /// Reverses [`interleave`], i.e. moves shards from
+    /// "even indexes first, then odd indexes" back to original order.
+    ///
+    /// E.g. shards `[0, 2, 4, 1, 3]` become `[0, 1, 2, 3, 4]`.
+    ///
+    /// [`interleave`]: ShardsRefMut::interleave
+    pub fn deinterleave(&mut self) {
+        deinterleave(self.data, self.shard_count, self.shard_len_64);
+    }
+
+    /// Creates new [`ShardsRefMut`] from a raw pointer to
+    /// `shard_count * shard_len_64` chunks of 64 bytes.
+    ///
+    /// This is for data allocated outside of Rust, e.g. output buffer of
+    /// C or CUDA code, which can then be processed with an [`Engine`]
+    /// without copying it first.
+    ///
+    /// # Safety
+    ///
+    /// Same as for [`core::slice::from_raw_parts_mut`]
+    /// with `len == shard_count * shard_len_64`, i.e.
+    /// - `ptr` must be non-null and aligned,
+    ///   also when the length is zero.
+    /// - `ptr` must be valid for reads and writes of
+    ///   `shard_count * shard_len_64` consecutive `[u8; 64]` chunks
+    ///   in a single allocation.
+    /// - The data must not be accessed through any other pointer
+    ///   and the allocation must not be freed for lifetime `'a`.
+    /// - `shard_count * shard_len_64 * 64` must not be larger than `isize::MAX`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simd_erasure_core::engine::{DefaultEngine, Engine, ShardsRefMut};
+    ///
+    /// // Stand-in for a buffer filled by e.g. a CUDA kernel.
+    /// let mut buffer = vec![[1u8; 64]; 3 * 2];
+    /// let ptr: *mut [u8; 64] = buffer.as_mut_ptr();
+    ///
+    /// {
+    ///     // SAFETY: `ptr` points to 6 chunks which are
+    ///     //         not accessed otherwise while `shards` exists.
+    ///     let mut shards = unsafe { ShardsRefMut::from_raw_parts(ptr, 3, 2) };
+    ///     assert_eq!(shards.len(), 3);
+    ///
+    ///     let engine = DefaultEngine::new();
+    ///     engine.mul(&mut shards[1], 12345);
+    /// }
+    ///
+    /// assert_eq!(buffer[0], [1; 64]);
+    /// assert_ne!(buffer[2], [1; 64]);
+    /// ```
+    pub unsafe fn from_raw_parts(
+        ptr: *mut [u8; 64],
+        shard_count: usize,
+        shard_len_64: usize,
+    ) -> Self {
+        let len = shard_count * shard_len_64;
+        Self::new(shard_count, shard_len_64, unsafe {
+            core::slice::from_raw_parts_mut(ptr, len)
+        })
+    }
+
+    /// Creates new [`ShardsRefMut`] that references given `data`,
+    /// first growing it with `0u8`:s if it's shorter than
+    /// `shard_count * shard_len_64`.
+    ///
+    /// This is like [`ShardsRefMut::new`] but doesn't require
+    /// `data` to be allocated to correct size beforehand.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simd_erasure_core::engine::ShardsRefMut;
+    ///
+    /// let mut data = Vec::new();
+    /// let shards = ShardsRefMut::from_vec_mut(4, 2, &mut data);
+    ///
+    /// assert_eq!(shards.len(), 4);
+    /// assert_eq!(data.len(), 8);
+    /// ```
+    pub fn from_vec_mut(
+        shard_count: usize,
+        shard_len_64: usize,
+        data: &'a mut Vec<[u8; 64]>,
+    ) -> Self {
+        let len = shard_count * shard_len_64;
+        if data.len() < len {
+            data.resize(len, [0; 64]);
+        }
+
+        Self::new(shard_count, shard_len_64, data)
+    }
+
+    /// Reorders shards in-place so that shards with even index come first,
+    /// followed by shards with odd index, both in their original order.
+    ///
+    /// E.g. shards `[0, 1, 2, 3, 4]` become `[0, 2, 4, 1, 3]`.
+    ///
+    /// This doesn't allocate. Shards are moved with rotations
+    /// so that each shard is moved `O(log n)` times.
+    pub fn interleave(&mut self) {
+        interleave(self.data, self.shard_count, self.shard_len_64);
+    }
+
     /// Returns `true` if this contains no shards.
     pub fn is_empty(&self) -> bool {
         self.shard_count == 0
     }
 
+    /// Returns `true` if number of shards is a power of two.
+    ///
+    /// This is `false` for an empty [`ShardsRefMut`].
+    pub fn is_power_of_two_count(&self) -> bool {
+        self.shard_count.is_power_of_two()
+    }
+
     /// Returns number of shards.
     pub fn len(&self) -> usize {
         self.shard_count
     }
 
+    /// Multiplies shards in given shard-`range` by `log_m`
+    /// using [`Engine::mul`] of given `engine`.
+    ///
+    /// This gives same result as calling `engine.mul` for each shard,
+    /// but as shards are stored contiguously the whole range
+    /// is passed to `engine` at once so that SIMD engines
+    /// can process it in a single batch.
+    ///
+    /// # Panics
+    ///
+    /// If `range.end > len()`.
+    pub fn mul_scalar_range<E: Engine>(
+        &mut self,
+        range: Range<usize>,
+        log_m: GfElement,
+        engine: &E,
+    ) {
+        assert!(range.end <= self.shard_count);
+
+        let start = range.start * self.shard_len_64;
+        let end = range.end * self.shard_len_64;
+        if start < end {
+            engine.mul(&mut self.data[start..end], log_m);
+        }
+    }
+
     /// Creates new [`ShardsRefMut`] that references given `data`.
     ///
     /// # Panics
@@ -188,8 +774,91 @@ impl<'a> ShardsRefMut<'a> {
         }
     }
 
+    /// Returns smallest power of two which is at least the number of shards,
+    /// i.e. the FFT size needed to process these shards.
+    ///
+    /// This is `1` for an empty [`ShardsRefMut`].
+    ///
+    /// # Examples
+    ///
+    /// Padding shards to a power of two is done by creating
+    /// a new [`ShardsRefMut`] over the same data
+    /// and zeroing the extra shards:
+    ///
+    /// ```rust
+    /// use simd_erasure_core::engine::ShardsRefMut;
+    ///
+    /// let mut data = vec![[1u8; 64]; 8];
+    /// let shards = ShardsRefMut::new(5, 1, &mut data);
+    /// assert!(!shards.is_power_of_two_count());
+    ///
+    /// let padded_count = shards.next_power_of_two_count();
+    /// assert_eq!(padded_count, 8);
+    ///
+    /// let mut padded = ShardsRefMut::new(padded_count, 1, &mut data);
+    /// padded.zero(5..);
+    /// assert!(padded.is_power_of_two_count());
+    /// ```
+    pub fn next_power_of_two_count(&self) -> usize {
+        self.shard_count.next_power_of_two()
+    }
+
+    /// Rotates shards in-place so that shard `mid` becomes the first shard.
+    ///
+    /// This is like [`slice::rotate_left`] but for whole shards
+    /// and doesn't allocate.
+    ///
+    /// # Panics
+    ///
+    /// If `mid > len()`.
+    pub fn rotate_left(&mut self, mid: usize) {
+        assert!(mid <= self.shard_count);
+        self.data.rotate_left(mid * self.shard_len_64);
+    }
+
+    /// Rotates shards in-place so that shard `len() - k` becomes the first shard.
+    ///
+    /// This is like [`slice::rotate_right`] but for whole shards
+    /// and doesn't allocate.
+    ///
+    /// # Panics
+    ///
+    /// If `k > len()`.
+    pub fn rotate_right(&mut self, k: usize) {
+        assert!(k <= self.shard_count);
+        self.data.rotate_right(k * self.shard_len_64);
+    }
+
     /// Splits this [`ShardsRefMut`] into two so that
     /// first includes shards `0..mid` and second includes shards `mid..`.
+    ///
+    /// Both halves borrow from `self`, so there is no need to join them
+    /// afterwards: Once they are dropped `self` again covers all shards,
+    /// without any copying.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simd_erasure_core::engine::ShardsRefMut;
+    ///
+    /// let mut data = vec![[0u8; 64]; 4];
+    /// let mut shards = ShardsRefMut::new(4, 1, &mut data);
+    ///
+    /// // Split and process halves separately, e.g. in parallel ...
+    /// let (mut first, mut second) = shards.split_at_mut(1);
+    /// first[0][0] = [1; 64];
+    /// second[2][0] = [2; 64];
+    ///
+    /// // ... and then continue with all shards.
+    /// assert_eq!(shards.len(), 4);
+    /// assert_eq!(shards[0][0], [1; 64]);
+    /// assert_eq!(shards[3][0], [2; 64]);
+    /// ```
+    ///
+    /// Halves never overlap and [`ShardsRefMut`] is [`Send`],
+    /// so they can be given to different threads, e.g. with `rayon::join`.
+    /// With `rayon` feature each half can also be consumed
+    /// into a parallel iterator over its shards.
     pub fn split_at_mut(&mut self, mid: usize) -> (ShardsRefMut<'_>, ShardsRefMut<'_>) {
         let (a, b) = self.data.split_at_mut(mid * self.shard_len_64);
 
@@ -199,6 +868,57 @@ impl<'a> ShardsRefMut<'a> {
         )
     }
 
+    /// Returns read-only view of shards in `range`.
+    ///
+    /// Unlike [`split_at_mut`] this borrows `self` only immutably,
+    /// so shards can still be read directly while the view exists.
+    ///
+    /// # Panics
+    ///
+    /// If `range.start > range.end` or `range.end > len()`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simd_erasure_core::engine::ShardsRefMut;
+    ///
+    /// let mut data = vec![[0u8; 64], [1; 64], [2; 64], [3; 64]];
+    /// let shards = ShardsRefMut::new(4, 1, &mut data);
+    ///
+    /// let view = shards.sub_view(1..3);
+    /// assert_eq!(view.len(), 2);
+    /// assert_eq!(view[0], shards[1]);
+    /// assert_eq!(view[1], shards[2]);
+    /// ```
+    ///
+    /// [`split_at_mut`]: ShardsRefMut::split_at_mut
+    pub fn sub_view(&self, range: Range<usize>) -> ShardsView<'_> {
+        assert!(range.start <= range.end && range.end <= self.shard_count);
+
+        ShardsView {
+            shard_count: range.end - range.start,
+            shard_len_64: self.shard_len_64,
+            data: &self.data[range.start * self.shard_len_64..range.end * self.shard_len_64],
+        }
+    }
+
+    /// Shortens this [`ShardsRefMut`] to first `new_len` shards.
+    ///
+    /// This is like [`Vec::truncate`] but the referenced data is
+    /// left untouched, only this view of it becomes shorter.
+    ///
+    /// # Panics
+    ///
+    /// If `new_len > len()`.
+    ///
+    /// [`Vec::truncate`]: alloc::vec::Vec::truncate
+    pub fn truncate(&mut self, new_len: usize) {
+        assert!(new_len <= self.shard_count);
+
+        self.data = &mut mem::take(&mut self.data)[..new_len * self.shard_len_64];
+        self.shard_count = new_len;
+    }
+
     /// Fills the given shard-range with `0u8`:s.
     pub fn zero<R: RangeBounds<usize>>(&mut self, range: R) {
         let start = match range.start_bound() {
@@ -218,29 +938,135 @@ impl<'a> ShardsRefMut<'a> {
 }
 
 // ======================================================================
-// ShardsRefMut - IMPL Index
+// ShardsRefMut - IMPL AsMut
 
-impl Index<usize> for ShardsRefMut<'_> {
-    type Output = [[u8; 64]];
-    fn index(&self, index: usize) -> &Self::Output {
-        &self.data[index * self.shard_len_64..(index + 1) * self.shard_len_64]
+/// Returns all shards as one flat slice of `len() * shard_len_64` chunks.
+impl AsMut<[[u8; 64]]> for ShardsRefMut<'_> {
+    fn as_mut(&mut self) -> &mut [[u8; 64]] {
+        self.data
     }
 }
 
 // ======================================================================
-// ShardsRefMut - IMPL IndexMut
+// ShardsRefMut - IMPL AsRef
 
-impl IndexMut<usize> for ShardsRefMut<'_> {
-    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-        &mut self.data[index * self.shard_len_64..(index + 1) * self.shard_len_64]
+/// Returns all shards as one flat slice of `len() * shard_len_64` chunks.
+impl AsRef<[[u8; 64]]> for ShardsRefMut<'_> {
+    fn as_ref(&self) -> &[[u8; 64]] {
+        self.data
     }
 }
 
 // ======================================================================
-// ShardsRefMut - CRATE
+// ShardsRefMut - IMPL Debug
 
-impl ShardsRefMut<'_> {
-    pub(crate) fn copy_within(&mut self, mut src: usize, mut dest: usize, mut count: usize) {
+// Like `Shards` only dimensions are shown, shards may contain user data.
+// With `debug-output` feature first 16 bytes of first shard are shown too.
+impl fmt::Debug for ShardsRefMut<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut f = f.debug_struct("ShardsRefMut");
+        f.field("shard_count", &self.shard_count)
+            .field("shard_len_64", &self.shard_len_64);
+
+        #[cfg(feature = "debug-output")]
+        if self.is_empty() {
+            f.field("first_shard_preview", &format_args!("(empty)"));
+        } else {
+            f.field("first_shard_preview", &DebugShard::<16>(&self[0]));
+        }
+
+        f.finish_non_exhaustive()
+    }
+}
+
+// ======================================================================
+// ShardsRefMut - IMPL Index
+
+impl Index<usize> for ShardsRefMut<'_> {
+    type Output = [[u8; 64]];
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.data[index * self.shard_len_64..(index + 1) * self.shard_len_64]
+    }
+}
+
+// ======================================================================
+// ShardsRefMut - IMPL IndexMut
+
+impl IndexMut<usize> for ShardsRefMut<'_> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.data[index * self.shard_len_64..(index + 1) * self.shard_len_64]
+    }
+}
+
+// ======================================================================
+// ShardsRefMut - IMPL IntoParallelIterator
+
+/// Parallel iterator over mutable shards, yielding `&mut [[u8; 64]]` per shard.
+///
+/// This makes [`par_iter_mut`] available for [`ShardsRefMut`]
+/// when `rayon` feature is enabled.
+///
+/// # Panics
+///
+/// If shard length is zero.
+///
+/// [`par_iter_mut`]: rayon::iter::IntoParallelRefMutIterator::par_iter_mut
+#[cfg(feature = "rayon")]
+impl<'data> rayon::iter::IntoParallelIterator for &'data mut ShardsRefMut<'_> {
+    type Iter = rayon::slice::ChunksExactMut<'data, [u8; 64]>;
+    type Item = &'data mut [[u8; 64]];
+
+    fn into_par_iter(self) -> Self::Iter {
+        use rayon::slice::ParallelSliceMut;
+
+        self.data.par_chunks_exact_mut(self.shard_len_64)
+    }
+}
+
+/// Parallel iterator over mutable shards, yielding `&mut [[u8; 64]]` per shard.
+///
+/// This is like `par_iter_mut` but consumes the [`ShardsRefMut`],
+/// so e.g. both halves from [`ShardsRefMut::split_at_mut`]
+/// can be processed in parallel for the whole lifetime `'a`.
+///
+/// # Panics
+///
+/// If shard length is zero.
+#[cfg(feature = "rayon")]
+impl<'a> rayon::iter::IntoParallelIterator for ShardsRefMut<'a> {
+    type Iter = rayon::slice::ChunksExactMut<'a, [u8; 64]>;
+    type Item = &'a mut [[u8; 64]];
+
+    fn into_par_iter(self) -> Self::Iter {
+        use rayon::slice::ParallelSliceMut;
+
+        self.data.par_chunks_exact_mut(self.shard_len_64)
+    }
+}
+
+// ======================================================================
+// ShardsRefMut - IMPL PartialEq
+
+/// Shards are equal if they have same shard count, same shard length
+/// and same contents.
+///
+/// This allows e.g. comparing results of two [`Engine`]:s directly.
+impl PartialEq for ShardsRefMut<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.shard_count == other.shard_count
+            && self.shard_len_64 == other.shard_len_64
+            && self.data[..self.shard_count * self.shard_len_64]
+                == other.data[..other.shard_count * other.shard_len_64]
+    }
+}
+
+impl Eq for ShardsRefMut<'_> {}
+
+// ======================================================================
+// ShardsRefMut - CRATE
+
+impl ShardsRefMut<'_> {
+    pub(crate) fn copy_within(&mut self, mut src: usize, mut dest: usize, mut count: usize) {
         src *= self.shard_len_64;
         dest *= self.shard_len_64;
         count *= self.shard_len_64;
@@ -271,3 +1097,944 @@ impl ShardsRefMut<'_> {
         }
     }
 }
+
+// ======================================================================
+// ShardsChunks - PUBLIC
+
+/// Iterator over non-overlapping sub-views of a [`ShardsRefMut`].
+///
+/// This struct is created by [`ShardsRefMut::chunks`].
+pub struct ShardsChunks<'a> {
+    chunk_size: usize,
+    // Remaining shards
+    shard_count: usize,
+    shard_len_64: usize,
+
+    data: &'a mut [[u8; 64]],
+}
+
+// ======================================================================
+// ShardsChunks - IMPL Iterator
+
+impl<'a> Iterator for ShardsChunks<'a> {
+    type Item = ShardsRefMut<'a>;
+    fn next(&mut self) -> Option<ShardsRefMut<'a>> {
+        if self.shard_count == 0 {
+            return None;
+        }
+
+        let count = core::cmp::min(self.chunk_size, self.shard_count);
+        let (head, tail) = mem::take(&mut self.data).split_at_mut(count * self.shard_len_64);
+
+        self.shard_count -= count;
+        self.data = tail;
+
+        Some(ShardsRefMut::new(count, self.shard_len_64, head))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.shard_count.div_ceil(self.chunk_size);
+        (remaining, Some(remaining))
+    }
+}
+
+// ======================================================================
+// ShardsChunks - IMPL ExactSizeIterator
+
+impl ExactSizeIterator for ShardsChunks<'_> {}
+
+// ======================================================================
+// ShardsChunks - PRIVATE
+
+impl<'a> ShardsChunks<'a> {
+    fn new(
+        chunk_size: usize,
+        shard_count: usize,
+        shard_len_64: usize,
+        data: &'a mut [[u8; 64]],
+    ) -> Self {
+        Self {
+            chunk_size,
+            shard_count,
+            shard_len_64,
+            data,
+        }
+    }
+}
+
+// ======================================================================
+// ShardsView - PUBLIC
+
+/// Read-only view of a range of shards of a [`ShardsRefMut`].
+///
+/// This struct is created by [`ShardsRefMut::sub_view`].
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct ShardsView<'a> {
+    shard_count: usize,
+    shard_len_64: usize,
+
+    data: &'a [[u8; 64]],
+}
+
+impl<'a> ShardsView<'a> {
+    /// Returns `true` if this view has no shards.
+    pub fn is_empty(&self) -> bool {
+        self.shard_count == 0
+    }
+
+    /// Returns iterator over shards of this view.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = &'a [[u8; 64]]> + 'a {
+        let (shard_len_64, data) = (self.shard_len_64, self.data);
+        (0..self.shard_count)
+            .map(move |index| &data[index * shard_len_64..(index + 1) * shard_len_64])
+    }
+
+    /// Returns number of shards in this view.
+    pub fn len(&self) -> usize {
+        self.shard_count
+    }
+}
+
+// ======================================================================
+// ShardsView - IMPL Index
+
+impl Index<usize> for ShardsView<'_> {
+    type Output = [[u8; 64]];
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.data[index * self.shard_len_64..(index + 1) * self.shard_len_64]
+    }
+}
+
+// ======================================================================
+// DebugAllShards - PRIVATE
+
+#[cfg(feature = "debug-output")]
+struct DebugAllShards<'a, 'b>(&'a ShardsRefMut<'b>);
+
+// ======================================================================
+// DebugAllShards - IMPL Debug
+
+#[cfg(feature = "debug-output")]
+impl fmt::Debug for DebugAllShards<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let shards = self.0;
+        f.debug_map()
+            .entries((0..shards.len()).map(|index| (index, DebugShard::<32>(&shards[index]))))
+            .finish()
+    }
+}
+
+// ======================================================================
+// DebugShard - PRIVATE
+
+#[cfg(feature = "debug-output")]
+struct DebugShard<'a, const MAX_BYTES: usize>(&'a [[u8; 64]]);
+
+// ======================================================================
+// DebugShard - IMPL Debug
+
+#[cfg(feature = "debug-output")]
+impl<const MAX_BYTES: usize> fmt::Debug for DebugShard<'_, MAX_BYTES> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bytes = self.0.as_flattened();
+        for byte in bytes.iter().take(MAX_BYTES) {
+            write!(f, "{byte:02x}")?;
+        }
+        if bytes.len() > MAX_BYTES {
+            f.write_str("..")?;
+        }
+        Ok(())
+    }
+}
+
+// ======================================================================
+// TESTS
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        engine::{utils, Naive, NoSimd},
+        test_util,
+    };
+
+    // Creates data where every byte of shard `i` is `i`.
+    fn numbered(shard_count: usize, shard_len_64: usize) -> Vec<[u8; 64]> {
+        let mut data = vec![[0; 64]; shard_count * shard_len_64];
+        for (i, shard) in data.chunks_exact_mut(shard_len_64).enumerate() {
+            shard.fill([i as u8; 64]);
+        }
+        data
+    }
+
+    fn shard_ids(shards: &ShardsRefMut) -> Vec<u8> {
+        (0..shards.len()).map(|i| shards[i][0][0]).collect()
+    }
+
+    // ============================================================
+    // Shards - Debug
+
+    #[test]
+    fn shards_debug() {
+        use alloc::format;
+
+        let mut shards = Shards::new();
+        shards.resize(3, 128);
+        shards.insert(1, &[123; 128]);
+
+        assert_eq!(
+            format!("{shards:?}"),
+            "Shards { shard_count: 3, shard_len_64: 2, data_len: 6, .. }"
+        );
+    }
+
+    // ============================================================
+    // ShardsRefMut - Debug
+
+    #[test]
+    fn shards_ref_mut_debug() {
+        use alloc::format;
+
+        let mut data = numbered(3, 2);
+        data[0][..16].copy_from_slice(&core::array::from_fn::<u8, 16, _>(|i| i as u8));
+        let shards = ShardsRefMut::new(3, 2, &mut data);
+
+        #[cfg(not(feature = "debug-output"))]
+        assert_eq!(
+            format!("{shards:?}"),
+            "ShardsRefMut { shard_count: 3, shard_len_64: 2, .. }"
+        );
+
+        #[cfg(feature = "debug-output")]
+        assert_eq!(
+            format!("{shards:?}"),
+            "ShardsRefMut { shard_count: 3, shard_len_64: 2, \
+             first_shard_preview: 000102030405060708090a0b0c0d0e0f.., .. }"
+        );
+
+        let mut data = numbered(0, 1);
+        let shards = ShardsRefMut::new(0, 1, &mut data);
+
+        #[cfg(not(feature = "debug-output"))]
+        assert_eq!(
+            format!("{shards:?}"),
+            "ShardsRefMut { shard_count: 0, shard_len_64: 1, .. }"
+        );
+
+        #[cfg(feature = "debug-output")]
+        assert_eq!(
+            format!("{shards:?}"),
+            "ShardsRefMut { shard_count: 0, shard_len_64: 1, first_shard_preview: (empty), .. }"
+        );
+    }
+
+    // ============================================================
+    // PartialEq
+
+    #[test]
+    fn partial_eq() {
+        let mut data = numbered(3, 2);
+        let mut same = numbered(3, 2);
+        let mut longer = numbered(4, 2);
+
+        let shards = ShardsRefMut::new(3, 2, &mut data);
+        assert!(shards == ShardsRefMut::new(3, 2, &mut same));
+        assert!(shards == ShardsRefMut::new(3, 2, &mut longer));
+        assert!(shards != ShardsRefMut::new(4, 2, &mut longer));
+        assert!(shards != ShardsRefMut::new(6, 1, &mut same));
+
+        same[5][63] ^= 1;
+        assert!(shards != ShardsRefMut::new(3, 2, &mut same));
+
+        let mut owned = Shards::new();
+        owned.resize(3, 128);
+        owned.data = numbered(3, 2);
+        assert!(shards == owned.as_ref_mut());
+
+        owned.resize(2, 128);
+        assert!(shards != owned.as_ref_mut());
+    }
+
+    #[test]
+    fn partial_eq_naive_nosimd() {
+        let mut naive_data = numbered(16, 2);
+        let mut nosimd_data = numbered(16, 2);
+        let mut naive = ShardsRefMut::new(16, 2, &mut naive_data);
+        let mut nosimd = ShardsRefMut::new(16, 2, &mut nosimd_data);
+
+        Naive::new().fft(&mut naive, 0, 16, 11, 16);
+        NoSimd::new().fft(&mut nosimd, 0, 16, 11, 16);
+        assert!(naive == nosimd);
+
+        Naive::new().ifft(&mut naive, 0, 16, 16, 16);
+        NoSimd::new().ifft(&mut nosimd, 0, 16, 16, 16);
+        assert!(naive == nosimd);
+    }
+
+    // ============================================================
+    // Shards::resize_preserve
+
+    fn numbered_shards(shard_count: usize, shard_len_64: usize) -> Shards {
+        let mut shards = Shards::new();
+        shards.resize(shard_count, shard_len_64 * 64);
+        shards.data = numbered(shard_count, shard_len_64);
+        shards
+    }
+
+    #[test]
+    fn resize_preserve_count() {
+        let mut shards = numbered_shards(3, 2);
+
+        shards.resize_preserve(5, 128);
+        assert_eq!(shard_ids(&shards.as_ref_mut()), [0, 1, 2, 0, 0]);
+        assert!(shards[3]
+            .iter()
+            .chain(shards[4].iter())
+            .all(|c| c == &[0; 64]));
+
+        shards.resize_preserve(2, 128);
+        assert_eq!(shards.data, numbered(2, 2));
+
+        shards.resize_preserve(3, 128);
+        assert_eq!(shards[2], [[0; 64]; 2]);
+    }
+
+    #[test]
+    fn resize_preserve_len() {
+        let mut shards = numbered_shards(3, 2);
+
+        shards.resize_preserve(4, 192);
+        for i in 0..3 {
+            assert_eq!(shards[i], [[i as u8; 64], [i as u8; 64], [0; 64]]);
+        }
+        assert_eq!(shards[3], [[0; 64]; 3]);
+
+        shards.resize_preserve(2, 64);
+        assert_eq!(shards.data, numbered(2, 1));
+    }
+
+    #[test]
+    fn shard_bytes_per_shard() {
+        let mut shards = Shards::new();
+        assert_eq!(shards.shard_bytes_per_shard(), 0);
+
+        shards.resize(3, 100);
+        assert_eq!(shards.shard_bytes_per_shard(), 100);
+        assert_eq!(shards.shard_len_64, 2);
+
+        shards.resize_preserve(2, 30);
+        assert_eq!(shards.shard_bytes_per_shard(), 30);
+        assert_eq!(shards.shard_len_64, 1);
+
+        // last chunk is made contiguous
+        shards.resize(1, 70);
+        shards.insert(0, &[[1; 64], [2; 64]].as_flattened()[57..127]);
+        shards.undo_last_chunk_encoding(0..1);
+        assert_eq!(
+            shards[0].as_flattened()[..70],
+            [[1; 64], [2; 64]].as_flattened()[57..127]
+        );
+    }
+
+    #[test]
+    fn insert_extract() {
+        let data: Vec<u8> = (0..=255).collect();
+        let mut shards = Shards::new();
+
+        for shard_bytes in [0, 2, 62, 64, 66, 100, 128, 130] {
+            shards.resize(2, shard_bytes);
+            shards.insert(1, &data[..shard_bytes]);
+            assert_eq!(shards.extract(1), &data[..shard_bytes]);
+        }
+
+        // last chunk has low and high half of tail at bytes 0 and 32
+        shards.resize(1, 66);
+        shards.insert(0, &data[..66]);
+        assert_eq!(shards[0][1][..1], [64]);
+        assert_eq!(shards[0][1][32..33], [65]);
+    }
+
+    #[test]
+    fn shard_ptr_range() {
+        let mut shards = numbered_shards(3, 2);
+        let base = shards.data.as_ptr().cast::<u8>();
+
+        for index in 0..3 {
+            let (ptr, len) = unsafe { shards.shard_ptr_range(index) };
+            assert_eq!(ptr, base.wrapping_add(index * 2 * 64));
+            assert_eq!(len, 2 * 64);
+            assert_eq!(ptr, shards[index].as_ptr().cast());
+
+            let bytes = unsafe { core::slice::from_raw_parts(ptr, len) };
+            assert_eq!(bytes, shards[index].as_flattened());
+        }
+
+        let (ptr, len) = unsafe { shards.shard_ptr_range_mut(1) };
+        unsafe { core::slice::from_raw_parts_mut(ptr, len) }.fill(9);
+        assert_eq!(shard_ids(&shards.as_ref_mut()), [0, 9, 2]);
+        assert_eq!(shards[2], [[2; 64]; 2]);
+    }
+
+    // ============================================================
+    // sub_view
+
+    #[test]
+    fn sub_view() {
+        let mut data = numbered(5, 2);
+        let mut shards = ShardsRefMut::new(5, 2, &mut data);
+
+        let view = shards.sub_view(1..4);
+        assert_eq!(view.len(), 3);
+        assert!(!view.is_empty());
+        for index in 0..3 {
+            assert_eq!(view[index], shards[index + 1]);
+        }
+        assert!(view.iter().eq((1..4).map(|index| &shards[index])));
+
+        // Parent is still readable while view exists.
+        assert_eq!(shard_ids(&shards), [0, 1, 2, 3, 4]);
+
+        assert!(view == shards.sub_view(1..4));
+        assert!(view != shards.sub_view(0..3));
+        assert!(shards.sub_view(2..2).is_empty());
+        assert_eq!(shards.sub_view(5..5).iter().len(), 0);
+
+        // ... and writable after it.
+        shards[2][0] = [9; 64];
+        assert_eq!(shards.sub_view(1..4)[1][0], [9; 64]);
+    }
+
+    #[test]
+    #[should_panic(expected = "range.end <= self.shard_count")]
+    fn sub_view_out_of_bounds() {
+        let mut data = numbered(3, 1);
+        ShardsRefMut::new(3, 1, &mut data).sub_view(2..4);
+    }
+
+    // ============================================================
+    // Shards::zero_range
+
+    #[test]
+    fn zero_range() {
+        let mut shards = numbered_shards(5, 2);
+
+        shards.zero_range(2..4);
+        assert_eq!(shards[1], [[1; 64]; 2]);
+        assert_eq!(shards[2], [[0; 64]; 2]);
+        assert_eq!(shards[3], [[0; 64]; 2]);
+        assert_eq!(shards[4], [[4; 64]; 2]);
+
+        shards.zero_range(4..4);
+        assert_eq!(shards[4], [[4; 64]; 2]);
+
+        shards.zero_range(0..5);
+        assert!(shards.data.iter().all(|c| c == &[0; 64]));
+    }
+
+    #[test]
+    #[should_panic(expected = "range.end <= self.shard_count")]
+    fn zero_range_out_of_bounds() {
+        numbered_shards(3, 1).zero_range(2..4);
+    }
+
+    // ============================================================
+    // as_io_slices / as_io_slices_mut
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn as_io_slices() {
+        let mut data = numbered(3, 2);
+        let shards = ShardsRefMut::new(3, 2, &mut data);
+
+        let slices = shards.as_io_slices(100);
+        assert_eq!(slices.len(), 3);
+        for (i, slice) in slices.iter().enumerate() {
+            assert_eq!(&slice[..], [i as u8; 100]);
+        }
+
+        assert!(shards.as_io_slices(0).iter().all(|slice| slice.is_empty()));
+        assert_eq!(shards.as_io_slices(128)[2].len(), 128);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn as_io_slices_mut() {
+        use std::io::Read;
+
+        let mut data = numbered(3, 2);
+        let mut shards = ShardsRefMut::new(3, 2, &mut data);
+
+        let input: Vec<u8> = (0..30).collect();
+        let mut slices = shards.as_io_slices_mut(10);
+        assert_eq!(slices.len(), 3);
+        assert_eq!(input.as_slice().read_vectored(&mut slices).unwrap(), 30);
+
+        for i in 0..3 {
+            let shard = shards[i].as_flattened();
+            assert_eq!(shard[..10], input[i * 10..(i + 1) * 10]);
+            assert!(shard[10..].iter().all(|&x| x == i as u8));
+        }
+    }
+
+    // ============================================================
+    // AsRef / AsMut
+
+    #[test]
+    fn as_ref_as_mut() {
+        let mut data = numbered(3, 2);
+        let ptr = data.as_ptr();
+        let mut shards = ShardsRefMut::new(2, 2, &mut data);
+
+        let flat: &[[u8; 64]] = shards.as_ref();
+        assert_eq!(flat.as_ptr(), ptr);
+        assert_eq!(flat.len(), 4);
+
+        utils::xor(shards.as_mut(), &[[1; 64]; 4]);
+        assert_eq!(shard_ids(&shards), [1, 0]);
+        assert_eq!(data[4], [2; 64]);
+    }
+
+    // ============================================================
+    // as_shard_pairs_mut
+
+    #[test]
+    fn as_shard_pairs_mut() {
+        let mut data = numbered(6, 2);
+        let mut shards = ShardsRefMut::new(6, 2, &mut data);
+
+        let mut count = 0;
+        for (i, (a, b)) in shards.as_shard_pairs_mut().enumerate() {
+            assert_eq!(a.len(), 2);
+            assert_eq!(b.len(), 2);
+            assert_eq!(a[0][0], 2 * i as u8);
+            assert_eq!(b[0][0], 2 * i as u8 + 1);
+
+            a[1] = [100; 64];
+            count += 1;
+        }
+        assert_eq!(count, 3);
+
+        assert_eq!(shard_ids(&shards), [0, 1, 2, 3, 4, 5]);
+        assert!((0..6).all(|i| shards[i][1] == [if i % 2 == 0 { 100 } else { i as u8 }; 64]));
+
+        let mut data = numbered(0, 2);
+        let mut shards = ShardsRefMut::new(0, 2, &mut data);
+        assert_eq!(shards.as_shard_pairs_mut().count(), 0);
+    }
+
+    // ============================================================
+    // bulk_xor_into
+
+    #[test]
+    fn bulk_xor_into() {
+        let mut data = vec![[0; 64]; 6 * 2];
+        for (i, chunk) in data.iter_mut().enumerate() {
+            *chunk = core::array::from_fn(|j| (i * 64 + j) as u8 ^ 0x5A);
+        }
+        let mut expected = data.clone();
+
+        let mut shards = ShardsRefMut::new(6, 2, &mut data);
+        shards.bulk_xor_into(0..4, 4);
+
+        let mut expected_shards = ShardsRefMut::new(6, 2, &mut expected);
+        for src in 0..4 {
+            let (dest, src) = expected_shards.flat2_mut(4, src, 1);
+            utils::xor(dest, src);
+        }
+
+        assert!(shards == expected_shards);
+
+        // empty range is no-op
+        shards.bulk_xor_into(2..2, 5);
+        assert!(shards == expected_shards);
+
+        // sources after dest
+        shards.bulk_xor_into(1..6, 0);
+        for src in 1..6 {
+            utils::xor_within(&mut expected_shards, 0, src, 1);
+        }
+        assert!(shards == expected_shards);
+    }
+
+    // ============================================================
+    // chunks
+
+    #[test]
+    fn chunks_cover_all_shards() {
+        for shard_count in 0..8 {
+            for chunk_size in 1..10 {
+                let mut data = numbered(shard_count, 2);
+                let mut shards = ShardsRefMut::new(shard_count, 2, &mut data);
+
+                let chunks = shards.chunks(chunk_size);
+                assert_eq!(chunks.len(), shard_count.div_ceil(chunk_size));
+
+                let mut ids = Vec::new();
+                for chunk in chunks {
+                    assert!(!chunk.is_empty());
+                    assert!(chunk.len() <= chunk_size);
+                    ids.extend(shard_ids(&chunk));
+                }
+
+                assert_eq!(ids, (0..shard_count as u8).collect::<Vec<_>>());
+            }
+        }
+    }
+
+    #[test]
+    fn chunks_are_disjoint() {
+        let mut data = numbered(5, 2);
+        let mut shards = ShardsRefMut::new(5, 2, &mut data);
+
+        let mut chunks: Vec<_> = shards.chunks(2).collect();
+        assert_eq!(
+            chunks.iter().map(ShardsRefMut::len).collect::<Vec<_>>(),
+            [2, 2, 1]
+        );
+
+        chunks[1][0].fill([100; 64]);
+        chunks[1].zero(1..);
+
+        assert_eq!(shard_ids(&chunks[0]), [0, 1]);
+        assert_eq!(shard_ids(&chunks[2]), [4]);
+        assert_eq!(shard_ids(&shards), [0, 1, 100, 0, 4]);
+    }
+
+    // ============================================================
+    // copy_from_slice_of_slices
+
+    #[test]
+    fn copy_from_slice_of_slices() {
+        let slices = test_util::generate_original(3, 100, 0);
+
+        let mut expected = Shards::new();
+        expected.resize(4, 100);
+        for (index, slice) in slices.iter().enumerate() {
+            expected.insert(index, slice);
+        }
+
+        let mut data = vec![[0; 64]; 4 * 2];
+        let mut shards = ShardsRefMut::new(4, 2, &mut data);
+        shards.copy_from_slice_of_slices(&slices, 100).unwrap();
+        assert!(shards == expected.as_ref_mut());
+
+        // nothing is copied on error
+        let mut invalid = slices.clone();
+        invalid[2].pop();
+        let zero = vec![0; 98];
+        assert_eq!(
+            shards.copy_from_slice_of_slices(&[&zero, &invalid[2]], 98),
+            Err(Error::DifferentShardSize {
+                shard_bytes: 98,
+                got: 99
+            })
+        );
+        assert_eq!(
+            shards.copy_from_slice_of_slices(&invalid, 100),
+            Err(Error::DifferentShardSize {
+                shard_bytes: 100,
+                got: 99
+            })
+        );
+        assert_eq!(
+            shards.copy_from_slice_of_slices(&[[0; 130]], 130),
+            Err(Error::InvalidShardSize { shard_bytes: 130 })
+        );
+        assert_eq!(
+            shards.copy_from_slice_of_slices(&[[0; 3]], 3),
+            Err(Error::InvalidShardSize { shard_bytes: 3 })
+        );
+        assert_eq!(
+            shards.copy_from_slice_of_slices(&[[0; 0]], 0),
+            Err(Error::InvalidShardSize { shard_bytes: 0 })
+        );
+        assert_eq!(
+            shards.copy_from_slice_of_slices(&[[0; 100]; 5], 100),
+            Err(Error::TooManyOriginalShards { original_count: 4 })
+        );
+        assert!(shards == expected.as_ref_mut());
+    }
+
+    // ============================================================
+    // debug_shard / debug_all_shards
+
+    #[cfg(feature = "debug-output")]
+    #[test]
+    fn debug_output() {
+        use alloc::format;
+
+        let mut data = numbered(3, 2);
+        data[2][0] = 0xf0;
+        let shards = ShardsRefMut::new(3, 2, &mut data);
+
+        assert_eq!(
+            format!("{:?}", shards.debug_shard(1)),
+            format!("f0{}..", "01".repeat(31))
+        );
+        assert_eq!(
+            format!("{:?}", shards.debug_all_shards()),
+            format!(
+                "{{0: {}.., 1: f0{}.., 2: {}..}}",
+                "00".repeat(32),
+                "01".repeat(31),
+                "02".repeat(32)
+            )
+        );
+
+        let mut data = numbered(0, 1);
+        let shards = ShardsRefMut::new(0, 1, &mut data);
+        assert_eq!(format!("{:?}", shards.debug_all_shards()), "{}");
+    }
+
+    // ============================================================
+    // from_vec_mut
+
+    #[test]
+    fn from_vec_mut() {
+        let mut data = Vec::new();
+        let mut shards = ShardsRefMut::from_vec_mut(3, 2, &mut data);
+        assert_eq!(shards.len(), 3);
+        shards[2][1] = [1; 64];
+        assert_eq!(data, [[0; 64], [0; 64], [0; 64], [0; 64], [0; 64], [1; 64]]);
+
+        // Longer data is kept as-is.
+        let mut data = numbered(4, 2);
+        let shards = ShardsRefMut::from_vec_mut(3, 2, &mut data);
+        assert_eq!(shard_ids(&shards), [0, 1, 2]);
+        assert_eq!(data, numbered(4, 2));
+    }
+
+    #[test]
+    fn from_raw_parts() {
+        let mut data = [[0u8; 64]; 6];
+        data[5] = [5; 64];
+
+        {
+            let mut shards = unsafe { ShardsRefMut::from_raw_parts(data.as_mut_ptr(), 3, 2) };
+            assert_eq!(shards.len(), 3);
+            assert_eq!(shards[2][1], [5; 64]);
+            shards[1][0] = [1; 64];
+        }
+
+        assert_eq!(data[2], [1; 64]);
+
+        let empty =
+            unsafe { ShardsRefMut::from_raw_parts(core::ptr::NonNull::dangling().as_ptr(), 0, 1) };
+        assert!(empty.is_empty());
+    }
+
+    // ============================================================
+    // interleave / deinterleave
+
+    #[test]
+    fn interleave() {
+        let mut data = numbered(5, 2);
+        let mut shards = ShardsRefMut::new(5, 2, &mut data);
+
+        shards.interleave();
+        assert_eq!(shard_ids(&shards), [0, 2, 4, 1, 3]);
+
+        shards.deinterleave();
+        assert_eq!(shard_ids(&shards), [0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn interleave_matches_gather() {
+        for shard_count in 0..40 {
+            for shard_len_64 in 1..3 {
+                let original = numbered(shard_count, shard_len_64);
+                let mut data = original.clone();
+                let mut shards = ShardsRefMut::new(shard_count, shard_len_64, &mut data);
+
+                let expected: Vec<u8> = (0..shard_count as u8)
+                    .step_by(2)
+                    .chain((1..shard_count as u8).step_by(2))
+                    .collect();
+
+                shards.interleave();
+                assert_eq!(shard_ids(&shards), expected);
+
+                shards.deinterleave();
+                assert_eq!(data, original);
+            }
+        }
+    }
+
+    // ============================================================
+    // mul_scalar_range
+
+    #[test]
+    fn mul_scalar_range() {
+        let engine = crate::engine::NoSimd::new();
+
+        let mut original = numbered(6, 2);
+        for (i, chunk) in original.iter_mut().enumerate() {
+            chunk[1] = i as u8 * 7;
+        }
+
+        for (start, end) in [(0, 6), (1, 4), (5, 6), (3, 3)] {
+            let mut data = original.clone();
+            let mut shards = ShardsRefMut::new(6, 2, &mut data);
+            shards.mul_scalar_range(start..end, 12345, &engine);
+
+            let mut expected = original.clone();
+            let mut expected_shards = ShardsRefMut::new(6, 2, &mut expected);
+            for i in start..end {
+                engine.mul(&mut expected_shards[i], 12345);
+            }
+
+            assert_eq!(data, expected);
+        }
+    }
+
+    // ============================================================
+    // next_power_of_two_count
+
+    #[test]
+    fn next_power_of_two_count() {
+        let mut data = vec![[0u8; 64]; 10];
+
+        for (shard_count, is_power_of_two, next) in [
+            (0, false, 1),
+            (1, true, 1),
+            (3, false, 4),
+            (4, true, 4),
+            (5, false, 8),
+        ] {
+            let shards = ShardsRefMut::new(shard_count, 2, &mut data);
+            assert_eq!(shards.is_power_of_two_count(), is_power_of_two);
+            assert_eq!(shards.next_power_of_two_count(), next);
+        }
+    }
+
+    // ============================================================
+    // par_iter_mut
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn par_iter_mut() {
+        use rayon::iter::{IndexedParallelIterator, IntoParallelRefMutIterator, ParallelIterator};
+
+        let mut data = numbered(100, 3);
+        let mut shards = ShardsRefMut::new(100, 3, &mut data);
+
+        assert_eq!(shards.par_iter_mut().len(), 100);
+
+        // XOR every shard in parallel with pattern derived from its index.
+        shards.par_iter_mut().enumerate().for_each(|(i, shard)| {
+            assert_eq!(shard.len(), 3);
+            for chunk in shard {
+                for x in chunk.iter_mut() {
+                    *x ^= (i as u8).wrapping_mul(3) ^ 0x55;
+                }
+            }
+        });
+
+        for i in 0..100 {
+            let expected = i as u8 ^ (i as u8).wrapping_mul(3) ^ 0x55;
+            assert!(shards[i].iter().all(|chunk| chunk == &[expected; 64]));
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn split_at_mut_into_par_iter() {
+        use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+        let mut data = numbered(10, 2);
+        let mut shards = ShardsRefMut::new(10, 2, &mut data);
+
+        let (first, second) = shards.split_at_mut(4);
+        rayon::join(
+            || {
+                first
+                    .into_par_iter()
+                    .for_each(|shard| shard.fill([0xAA; 64]));
+            },
+            || {
+                second
+                    .into_par_iter()
+                    .for_each(|shard| shard.fill([0x55; 64]));
+            },
+        );
+
+        for i in 0..10 {
+            let expected = if i < 4 { 0xAA } else { 0x55 };
+            assert!(shards[i].iter().all(|chunk| chunk == &[expected; 64]));
+        }
+    }
+
+    // ============================================================
+    // rotate_left / rotate_right
+
+    #[test]
+    fn rotate_left() {
+        let mut data = numbered(5, 2);
+        let mut shards = ShardsRefMut::new(5, 2, &mut data);
+
+        shards.rotate_left(2);
+        assert_eq!(shard_ids(&shards), [2, 3, 4, 0, 1]);
+    }
+
+    #[test]
+    fn rotate_right() {
+        let mut data = numbered(5, 2);
+        let mut shards = ShardsRefMut::new(5, 2, &mut data);
+
+        shards.rotate_right(2);
+        assert_eq!(shard_ids(&shards), [3, 4, 0, 1, 2]);
+    }
+
+    #[test]
+    fn rotate_left_then_right() {
+        for shard_count in 1..8 {
+            for shard_len_64 in 1..3 {
+                let original = numbered(shard_count, shard_len_64);
+
+                for k in 0..=shard_count {
+                    let mut data = original.clone();
+                    let mut shards = ShardsRefMut::new(shard_count, shard_len_64, &mut data);
+
+                    shards.rotate_left(k);
+                    shards.rotate_right(k);
+                    assert_eq!(data, original);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn rotate_by_zero_and_len() {
+        let original = numbered(4, 1);
+        let mut data = original.clone();
+        let mut shards = ShardsRefMut::new(4, 1, &mut data);
+
+        shards.rotate_left(0);
+        shards.rotate_right(0);
+        shards.rotate_left(4);
+        shards.rotate_right(4);
+        assert_eq!(data, original);
+    }
+
+    // ============================================================
+    // truncate
+
+    #[test]
+    fn truncate() {
+        let mut data = numbered(5, 2);
+        let mut shards = ShardsRefMut::new(5, 2, &mut data);
+
+        shards.truncate(5);
+        assert_eq!(shards.len(), 5);
+
+        shards.truncate(3);
+        assert_eq!(shard_ids(&shards), [0, 1, 2]);
+
+        shards.zero(..);
+        shards.truncate(0);
+        assert!(shards.is_empty());
+
+        assert_eq!(data[..6], [[0; 64]; 6]);
+        assert_eq!(data[6..], [[3; 64], [3; 64], [4; 64], [4; 64]]);
+    }
+}
@@ -1,6 +1,7 @@
 //! Lookup-tables used by [`Engine`]:s.
 //!
 //! All tables are global and each is initialized at most once.
+//! Tables of a custom [`GfField`] are held by [`FieldTables`] instead.
 //!
 //! # Tables
 //!
@@ -13,24 +14,49 @@
 //! | [`Mul128`]   | 8 MiB   | yes              | yes              | [`Avx2`] [`Ssse3`] |
 //! | [`Skew`]     | 128 kiB | yes              | yes              | all                |
 //!
+//! # Exponentiation and logarithm
+//!
+//! [`Exp`] and [`Log`] are inverses of each other:
+//! `exp[log[x]] == x` for every nonzero element `x`
+//! and `log[exp[i]] == i` for every `i` in `0..GF_MODULUS`.
+//! Elements are represented in Cantor basis.
+//!
+//! Tables are built once and then shared, so there's no need
+//! to build or cache them separately. Tables of the default field
+//! are returned by [`get_exp_log`] and those of a custom field
+//! by [`FieldTables::exp_log`]. [`mul`] multiplies using them.
+//! [`build_gf_tables`] builds new tables as `Vec`:s on every call.
+//!
+//! ```rust
+//! use simd_erasure_core::engine::{tables, GF_MODULUS, GF_ORDER};
+//!
+//! let exp_log = tables::get_exp_log();
+//!
+//! for x in 1..GF_ORDER {
+//!     assert_eq!(exp_log.exp[exp_log.log[x] as usize] as usize, x);
+//! }
+//!
+//! for i in 0..GF_MODULUS {
+//!     assert_eq!(exp_log.log[exp_log.exp[i as usize] as usize], i);
+//! }
+//! ```
+//!
 //! [`NoSimd`]: crate::engine::NoSimd
 //! [`Avx2`]: crate::engine::Avx2
 //! [`Ssse3`]: crate::engine::Ssse3
 //! [`Engine`]: crate::engine
+//! [`GfField`]: crate::engine::GfField
 //!
 
 #[cfg(not(feature = "std"))]
 use alloc::boxed::Box;
 #[cfg(not(feature = "std"))]
-use alloc::vec;
-#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
 use once_cell::race::OnceBox;
 #[cfg(feature = "std")]
 use std::sync::LazyLock;
 
-use crate::engine::{
-    fwht, utils, GfElement, CANTOR_BASIS, GF_BITS, GF_MODULUS, GF_ORDER, GF_POLYNOMIAL,
-};
+use crate::engine::{fwht, utils, GfElement, GfField, GfPolynomial, GF_BITS, GF_MODULUS, GF_ORDER};
 
 // ======================================================================
 // TYPE ALIASES - PUBLIC
@@ -77,6 +103,19 @@ pub type Mul16 = [[[GfElement; 16]; 4]; GF_ORDER];
 
 /// Used by all [`Engine`]:s for FFT and IFFT.
 ///
+/// Table has one entry per butterfly position, `GF_MODULUS` in total.
+/// Butterflies of layer with distance `dist` over shards `r .. r + 2 * dist`
+/// use entry `skew_delta + r + dist - 1` as their `log_m`,
+/// i.e. the logarithm of the twisting factor of those butterflies.
+///
+/// Entry `GF_MODULUS` means factor zero, in which case
+/// butterfly is just XOR. This is the case e.g. for the first
+/// butterflies of each layer with `skew_delta == 0`, at index `dist - 1`.
+///
+/// This is a table of logarithms in Cantor basis,
+/// not a table of roots of unity `ω^(i * j)`:
+/// the additive FFT used here has no multiplicative roots of unity.
+///
 /// [`Engine`]: crate::engine
 pub type Skew = [GfElement; GF_MODULUS as usize];
 
@@ -91,20 +130,113 @@ pub struct ExpLog {
     pub log: Box<Log>,
 }
 
+// ======================================================================
+// FieldTables - PUBLIC
+
+/// Lookup-tables of a custom [`GfField`].
+///
+/// Each table is initialized lazily at most once,
+/// when it's first needed.
+///
+/// [`GfField`]: crate::engine::GfField
+pub struct FieldTables {
+    field: GfField,
+    exp_log: OnceBox<ExpLog>,
+    log_walsh: OnceBox<LogWalsh>,
+    mul16: OnceBox<Mul16>,
+    skew: OnceBox<Skew>,
+}
+
+impl FieldTables {
+    /// Lazily initialized exponentiation and logarithm tables.
+    pub fn exp_log(&self) -> &ExpLog {
+        self.exp_log
+            .get_or_init(|| Box::new(initialize_exp_log(self.field)))
+    }
+
+    /// Returns field of these tables.
+    pub fn field(&self) -> GfField {
+        self.field
+    }
+
+    /// Lazily initialized logarithmic Walsh transform table.
+    pub fn log_walsh(&self) -> &LogWalsh {
+        self.log_walsh
+            .get_or_init(|| initialize_log_walsh(&self.exp_log().log))
+    }
+
+    /// Lazily initialized multiplication table for the `NoSimd` engine.
+    pub fn mul16(&self) -> &Mul16 {
+        self.mul16.get_or_init(|| initialize_mul16(self.exp_log()))
+    }
+
+    /// Creates new [`FieldTables`] without initializing any tables.
+    pub fn new(field: GfField) -> Self {
+        Self {
+            field,
+            exp_log: OnceBox::new(),
+            log_walsh: OnceBox::new(),
+            mul16: OnceBox::new(),
+            skew: OnceBox::new(),
+        }
+    }
+
+    /// Lazily initialized skew table used in FFT and IFFT operations.
+    pub fn skew(&self) -> &Skew {
+        self.skew.get_or_init(|| initialize_skew(self.exp_log()))
+    }
+}
+
+// ======================================================================
+// FieldTablesNode - PRIVATE
+
+// Node of append-only list of `FieldTables`, one per field,
+// used by `get_field_tables`. Nodes are never removed.
+struct FieldTablesNode {
+    tables: FieldTables,
+    next: OnceBox<FieldTablesNode>,
+}
+
 // ======================================================================
 // STATIC - PUBLIC
 
+/// Returns [`FieldTables`] of given `field`, creating them on first call.
+///
+/// Same tables are returned for every call with the same `field`,
+/// so tables of each field are initialized at most once
+/// and kept for the lifetime of the program.
+pub fn get_field_tables(field: GfField) -> &'static FieldTables {
+    static HEAD: OnceBox<FieldTablesNode> = OnceBox::new();
+
+    let mut slot = &HEAD;
+    loop {
+        // If another thread fills this slot first, with any field,
+        // our node is dropped and we continue with its node.
+        let node = slot.get_or_init(|| {
+            Box::new(FieldTablesNode {
+                tables: FieldTables::new(field),
+                next: OnceBox::new(),
+            })
+        });
+
+        if node.tables.field() == field {
+            return &node.tables;
+        }
+        slot = &node.next;
+    }
+}
+
 /// Lazily initialized exponentiation and logarithm tables.
 pub fn get_exp_log() -> &'static ExpLog {
     #[cfg(feature = "std")]
     {
-        static EXP_LOG: LazyLock<ExpLog> = LazyLock::new(initialize_exp_log);
+        static EXP_LOG: LazyLock<ExpLog> = LazyLock::new(|| initialize_exp_log(GfField::DEFAULT));
         &EXP_LOG
     }
     #[cfg(not(feature = "std"))]
     {
         static EXP_LOG: OnceBox<ExpLog> = OnceBox::new();
-        EXP_LOG.get_or_init(|| Box::new(initialize_exp_log()))
+        EXP_LOG.get_or_init(|| Box::new(initialize_exp_log(GfField::DEFAULT)))
     }
 }
 
@@ -112,13 +244,14 @@ pub fn get_exp_log() -> &'static ExpLog {
 pub fn get_log_walsh() -> &'static LogWalsh {
     #[cfg(feature = "std")]
     {
-        static LOG_WALSH: LazyLock<Box<LogWalsh>> = LazyLock::new(initialize_log_walsh);
+        static LOG_WALSH: LazyLock<Box<LogWalsh>> =
+            LazyLock::new(|| initialize_log_walsh(&get_exp_log().log));
         &LOG_WALSH
     }
     #[cfg(not(feature = "std"))]
     {
         static LOG_WALSH: OnceBox<LogWalsh> = OnceBox::new();
-        LOG_WALSH.get_or_init(initialize_log_walsh)
+        LOG_WALSH.get_or_init(|| initialize_log_walsh(&get_exp_log().log))
     }
 }
 
@@ -126,13 +259,13 @@ pub fn get_log_walsh() -> &'static LogWalsh {
 pub fn get_mul16() -> &'static Mul16 {
     #[cfg(feature = "std")]
     {
-        static MUL16: LazyLock<Box<Mul16>> = LazyLock::new(initialize_mul16);
+        static MUL16: LazyLock<Box<Mul16>> = LazyLock::new(|| initialize_mul16(get_exp_log()));
         &MUL16
     }
     #[cfg(not(feature = "std"))]
     {
         static MUL16: OnceBox<Mul16> = OnceBox::new();
-        MUL16.get_or_init(initialize_mul16)
+        MUL16.get_or_init(|| initialize_mul16(get_exp_log()))
     }
 }
 
@@ -140,30 +273,82 @@ pub fn get_mul16() -> &'static Mul16 {
 pub fn get_mul128() -> &'static Mul128 {
     #[cfg(feature = "std")]
     {
-        static MUL128: LazyLock<Box<Mul128>> = LazyLock::new(initialize_mul128);
+        static MUL128: LazyLock<Box<Mul128>> = LazyLock::new(|| initialize_mul128(get_exp_log()));
         &MUL128
     }
     #[cfg(not(feature = "std"))]
     {
         static MUL128: OnceBox<Mul128> = OnceBox::new();
-        MUL128.get_or_init(initialize_mul128)
+        MUL128.get_or_init(|| initialize_mul128(get_exp_log()))
     }
 }
 
 /// Lazily initialized skew table used in FFT and IFFT operations.
+///
+/// This is the table used by all engines with the default field,
+/// so e.g. custom transforms built with [`Engine::fft_with_skew`]
+/// can share it instead of building their own. See [`Skew`] for details.
+///
+/// [`Engine::fft_with_skew`]: crate::engine::Engine::fft_with_skew
 pub fn get_skew() -> &'static Skew {
     #[cfg(feature = "std")]
     {
-        static SKEW: LazyLock<Box<Skew>> = LazyLock::new(initialize_skew);
+        static SKEW: LazyLock<Box<Skew>> = LazyLock::new(|| initialize_skew(get_exp_log()));
         &SKEW
     }
     #[cfg(not(feature = "std"))]
     {
         static SKEW: OnceBox<Skew> = OnceBox::new();
-        SKEW.get_or_init(initialize_skew)
+        SKEW.get_or_init(|| initialize_skew(get_exp_log()))
     }
 }
 
+// ======================================================================
+// FUNCTIONS - PUBLIC - build tables
+
+/// Builds new [`Log`] and [`Exp`] tables of field with polynomial `poly`
+/// and generator `2`, returned as `(log, exp)`.
+///
+/// `poly` includes the `x^16` term, e.g. [`GF_POLYNOMIAL`] `0x1002D`,
+/// same as returned by [`Engine::gf_polynomial`].
+///
+/// Tables are built on every call. Use [`get_exp_log`]
+/// or [`get_field_tables`] to get tables which are built only once.
+///
+/// # Panics
+///
+/// Panics if `poly` isn't a polynomial of degree 16
+/// or if `2` isn't a generator of the field defined by it.
+///
+/// # Examples
+///
+/// ```rust
+/// use simd_erasure_core::engine::{tables, GF_POLYNOMIAL};
+///
+/// let (log, exp) = tables::build_gf_tables(GF_POLYNOMIAL as u32);
+///
+/// assert_eq!(log[..], tables::get_exp_log().log[..]);
+/// assert_eq!(exp[..], tables::get_exp_log().exp[..]);
+/// ```
+///
+/// [`GF_POLYNOMIAL`]: crate::engine::GF_POLYNOMIAL
+/// [`Engine::gf_polynomial`]: crate::engine::Engine::gf_polynomial
+pub fn build_gf_tables(poly: u32) -> (Vec<GfElement>, Vec<GfElement>) {
+    assert!(
+        poly >> GF_BITS == 1,
+        "polynomial {poly:#x} isn't of degree {GF_BITS}"
+    );
+
+    let field = GfField::new(GfPolynomial(poly as GfElement), 2)
+        .unwrap_or_else(|| panic!("2 isn't a generator of field with polynomial {poly:#x}"));
+
+    let ExpLog { exp, log } = initialize_exp_log(field);
+    (
+        (log as Box<[GfElement]>).into_vec(),
+        (exp as Box<[GfElement]>).into_vec(),
+    )
+}
+
 // ======================================================================
 // FUNCTIONS - PUBLIC - math
 
@@ -181,7 +366,7 @@ pub fn mul(x: GfElement, log_m: GfElement, exp: &Exp, log: &Log) -> GfElement {
 // FUNCTIONS - PRIVATE - initialize tables
 
 #[allow(clippy::needless_range_loop)]
-fn initialize_exp_log() -> ExpLog {
+fn initialize_exp_log(field: GfField) -> ExpLog {
     let mut exp = Box::new([0; GF_ORDER]);
     let mut log = Box::new([0; GF_ORDER]);
 
@@ -189,21 +374,20 @@ fn initialize_exp_log() -> ExpLog {
 
     let mut state = 1;
     for i in 0..GF_MODULUS {
-        exp[state] = i;
-        state <<= 1;
-        if state >= GF_ORDER {
-            state ^= GF_POLYNOMIAL;
-        }
+        exp[state as usize] = i;
+        state = field.mul(state, field.generator());
     }
     exp[0] = GF_MODULUS;
 
     // CONVERT TO CANTOR BASIS
 
+    let cantor_basis = field.cantor_basis();
+
     log[0] = 0;
     for i in 0..GF_BITS {
         let width = 1usize << i;
         for j in 0..width {
-            log[j + width] = log[j] ^ CANTOR_BASIS[i];
+            log[j + width] = log[j] ^ cantor_basis[i];
         }
     }
 
@@ -220,9 +404,7 @@ fn initialize_exp_log() -> ExpLog {
     ExpLog { exp, log }
 }
 
-fn initialize_log_walsh() -> Box<LogWalsh> {
-    let log = get_exp_log().log.as_slice();
-
+fn initialize_log_walsh(log: &Log) -> Box<LogWalsh> {
     let mut log_walsh: Box<LogWalsh> = Box::new([0; GF_ORDER]);
 
     log_walsh.copy_from_slice(log);
@@ -232,9 +414,9 @@ fn initialize_log_walsh() -> Box<LogWalsh> {
     log_walsh
 }
 
-fn initialize_mul16() -> Box<Mul16> {
-    let exp = &get_exp_log().exp;
-    let log = &get_exp_log().log;
+fn initialize_mul16(exp_log: &ExpLog) -> Box<Mul16> {
+    let exp = &exp_log.exp;
+    let log = &exp_log.log;
     let mut mul16 = vec![[[0; 16]; 4]; GF_ORDER];
 
     for log_m in 0..=GF_MODULUS {
@@ -250,11 +432,11 @@ fn initialize_mul16() -> Box<Mul16> {
     mul16.into_boxed_slice().try_into().unwrap()
 }
 
-fn initialize_mul128() -> Box<Mul128> {
+fn initialize_mul128(exp_log: &ExpLog) -> Box<Mul128> {
     // Based on:
     // https://github.com/catid/leopard/blob/22ddc7804998d31c8f1a2617ee720e063b1fa6cd/LeopardFF16.cpp#L375
-    let exp = &get_exp_log().exp;
-    let log = &get_exp_log().log;
+    let exp = &exp_log.exp;
+    let log = &exp_log.log;
 
     let mut mul128 = vec![
         Multiply128lutT {
@@ -282,9 +464,9 @@ fn initialize_mul128() -> Box<Mul128> {
 }
 
 #[allow(clippy::needless_range_loop)]
-fn initialize_skew() -> Box<Skew> {
-    let exp = &get_exp_log().exp;
-    let log = &get_exp_log().log;
+fn initialize_skew(exp_log: &ExpLog) -> Box<Skew> {
+    let exp = &exp_log.exp;
+    let log = &exp_log.log;
 
     let mut skew = Box::new([0; GF_MODULUS as usize]);
 
@@ -322,3 +504,82 @@ fn initialize_skew() -> Box<Skew> {
 
     skew
 }
+
+// ======================================================================
+// TESTS
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::GF_POLYNOMIAL;
+
+    fn assert_exp_log_inverse(exp_log: &ExpLog) {
+        for x in 1..GF_ORDER {
+            assert_eq!(exp_log.exp[exp_log.log[x] as usize] as usize, x);
+        }
+
+        for i in 0..GF_MODULUS {
+            assert_eq!(exp_log.log[exp_log.exp[i as usize] as usize], i);
+        }
+    }
+
+    #[test]
+    fn skew_first_butterfly_of_each_layer_is_xor() {
+        let skew = get_skew();
+        assert_eq!(skew.len(), GF_MODULUS as usize);
+
+        for m in 0..GF_BITS - 1 {
+            assert_eq!(skew[(1 << m) - 1], GF_MODULUS);
+        }
+    }
+
+    #[test]
+    fn get_field_tables() {
+        let field_a = GfField::new(GfPolynomial(0x100B), 2).unwrap();
+        let field_b = GfField::new(GfPolynomial(0x002D), 2).unwrap();
+
+        let a = super::get_field_tables(field_a);
+        let b = super::get_field_tables(field_b);
+
+        assert_eq!(a.field(), field_a);
+        assert_eq!(b.field(), field_b);
+        assert!(core::ptr::eq(a, super::get_field_tables(field_a)));
+        assert!(core::ptr::eq(b, super::get_field_tables(field_b)));
+        assert!(!core::ptr::eq(a, b));
+    }
+
+    #[test]
+    fn build_gf_tables() {
+        for poly in [GF_POLYNOMIAL as u32, 0x1100B] {
+            let (log, exp) = super::build_gf_tables(poly);
+            assert_eq!(log.len(), GF_ORDER);
+            assert_eq!(exp.len(), GF_ORDER);
+
+            for x in 1..GF_ORDER {
+                assert_eq!(exp[log[x] as usize] as usize, x, "poly = {poly:#x}");
+            }
+
+            for i in 0..GF_MODULUS {
+                assert_eq!(log[exp[i as usize] as usize], i, "poly = {poly:#x}");
+            }
+        }
+
+        let (log, exp) = super::build_gf_tables(GF_POLYNOMIAL as u32);
+        assert_eq!(log[..], get_exp_log().log[..]);
+        assert_eq!(exp[..], get_exp_log().exp[..]);
+    }
+
+    #[test]
+    #[should_panic(expected = "isn't of degree 16")]
+    fn build_gf_tables_not_degree_16() {
+        super::build_gf_tables(0x100B);
+    }
+
+    #[test]
+    fn exp_log_inverse() {
+        assert_exp_log_inverse(get_exp_log());
+
+        let field = GfField::new(GfPolynomial(0x100B), 2).unwrap();
+        assert_exp_log_inverse(FieldTables::new(field).exp_log());
+    }
+}
@@ -2,7 +2,11 @@
 //!
 //! [`Engine`]: crate::engine::Engine
 
-use crate::engine::{fwht, tables, Engine, GfElement, ShardsRefMut, GF_BITS, GF_ORDER};
+use crate::engine::{
+    fwht,
+    tables::{self, ExpLog, LogWalsh},
+    Engine, GfElement, ShardsRefMut, GF_BITS, GF_MODULUS, GF_ORDER,
+};
 use core::iter::zip;
 
 // ======================================================================
@@ -18,8 +22,23 @@ use core::iter::zip;
 /// [`Avx2`]: crate::engine::Avx2
 #[inline(always)]
 pub fn eval_poly(erasures: &mut [GfElement; GF_ORDER], truncated_size: usize) {
-    let log_walsh = tables::get_log_walsh();
+    eval_poly_with_log_walsh(erasures, truncated_size, tables::get_log_walsh());
+}
 
+/// Evaluate Polynomial using Fast Walsh-Hadamard Transform (FWHT)
+/// and given [`LogWalsh`] table.
+///
+/// This is same as [`eval_poly`] but can be used with
+/// tables of a custom [`GfField`], see [`FieldTables`].
+///
+/// [`GfField`]: crate::engine::GfField
+/// [`FieldTables`]: crate::engine::tables::FieldTables
+#[inline(always)]
+pub fn eval_poly_with_log_walsh(
+    erasures: &mut [GfElement; GF_ORDER],
+    truncated_size: usize,
+    log_walsh: &LogWalsh,
+) {
     fwht::fwht(erasures, truncated_size);
 
     for (e, factor) in zip(erasures.iter_mut(), log_walsh.iter()) {
@@ -30,6 +49,52 @@ pub fn eval_poly(erasures: &mut [GfElement; GF_ORDER], truncated_size: usize) {
     fwht::fwht(erasures, GF_ORDER);
 }
 
+/// Returns logarithm of the multiplicative inverse of element
+/// with logarithm `log_m`, i.e. multiplying with [`Engine::mul`]
+/// first by `log_m` and then by `mul_inverse(log_m)` is a no-op.
+///
+/// In log space this is `GF_MODULUS - log_m`.
+///
+/// # Examples
+///
+/// ```rust
+/// use simd_erasure_core::engine::{utils, Engine, NoSimd};
+///
+/// let engine = NoSimd::new();
+/// let mut x = vec![[123u8; 64]; 1];
+///
+/// engine.mul(&mut x, 12345);
+/// engine.mul(&mut x, utils::mul_inverse(12345));
+/// assert_eq!(x, vec![[123u8; 64]; 1]);
+/// ```
+pub fn mul_inverse(log_m: GfElement) -> GfElement {
+    GF_MODULUS - log_m
+}
+
+/// `x[i] = 1 / x[i]` for each Galois field element `x[i]` of `x`
+/// using given [`Exp`] and [`Log`] tables, `0` stays `0`.
+///
+/// This is the default implementation of [`Engine::mul_inverse_shard`]
+/// and can be used with tables of a custom [`GfField`], see [`FieldTables`].
+///
+/// [`Exp`]: crate::engine::tables::Exp
+/// [`Log`]: crate::engine::tables::Log
+/// [`GfField`]: crate::engine::GfField
+/// [`FieldTables`]: crate::engine::tables::FieldTables
+pub fn mul_inverse_shard_with_exp_log(x: &mut [[u8; 64]], exp_log: &ExpLog) {
+    for chunk in x.iter_mut() {
+        for i in 0..32 {
+            let value = GfElement::from(chunk[i]) | (GfElement::from(chunk[i + 32]) << 8);
+            if value != 0 {
+                let log_inverse = mul_inverse(exp_log.log[value as usize]);
+                let inverse = exp_log.exp[log_inverse as usize];
+                chunk[i] = inverse as u8;
+                chunk[i + 32] = (inverse >> 8) as u8;
+            }
+        }
+    }
+}
+
 /// `x[] ^= y[]`
 #[inline(always)]
 pub fn xor(xs: &mut [[u8; 64]], ys: &[[u8; 64]]) {
@@ -71,6 +136,21 @@ pub(crate) fn sub_mod(x: GfElement, y: GfElement) -> GfElement {
 // ======================================================================
 // FUNCTIONS - CRATE
 
+/// Number of butterflies in FFT or IFFT of given `size` and `truncated_size`.
+///
+/// This follows the butterfly structure of [`Naive`] engine.
+///
+/// [`Naive`]: crate::engine::Naive
+pub(crate) fn butterfly_count(size: usize, truncated_size: usize) -> usize {
+    let mut count = 0;
+    let mut dist = size / 2;
+    while dist > 0 {
+        count += truncated_size.div_ceil(dist * 2) * dist;
+        dist /= 2;
+    }
+    count
+}
+
 /// FFT with `skew_delta = pos + size`.
 #[inline(always)]
 pub(crate) fn fft_skew_end(
@@ -102,3 +182,52 @@ pub(crate) fn formal_derivative(data: &mut ShardsRefMut) {
         xor_within(data, i - width, i, width);
     }
 }
+
+// ======================================================================
+// TESTS
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::NoSimd;
+
+    #[test]
+    fn mul_inverse() {
+        let engine = NoSimd::new();
+        let original: [[u8; 64]; 1] = [core::array::from_fn(|i| i as u8 * 3 + 1)];
+
+        for log_m in [0, 1, 2, 3, 255, 256, 12345, GF_MODULUS - 1, GF_MODULUS] {
+            let mut x = original;
+            engine.mul(&mut x, log_m);
+            engine.mul(&mut x, super::mul_inverse(log_m));
+            assert_eq!(x, original, "log_m = {log_m}");
+        }
+    }
+
+    #[test]
+    fn mul_inverse_shard_with_exp_log() {
+        let exp_log = tables::get_exp_log();
+        let one = exp_log.exp[0];
+
+        for value in 0..GF_ORDER {
+            let mut x = [[0u8; 64]; 1];
+            x[0][5] = value as u8;
+            x[0][5 + 32] = (value >> 8) as u8;
+
+            super::mul_inverse_shard_with_exp_log(&mut x, exp_log);
+            let inverse = GfElement::from(x[0][5]) | (GfElement::from(x[0][5 + 32]) << 8);
+
+            if value == 0 {
+                assert_eq!(inverse, 0);
+            } else {
+                let product = tables::mul(
+                    value as GfElement,
+                    exp_log.log[inverse as usize],
+                    &exp_log.exp,
+                    &exp_log.log,
+                );
+                assert_eq!(product, one, "value = {value}");
+            }
+        }
+    }
+}
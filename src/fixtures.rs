@@ -0,0 +1,107 @@
+//! Deterministic test data for crates building on this one.
+//!
+//! This module is available with `fixtures` feature.
+//!
+//! Shards are filled with output of `ChaCha8` PRNG seeded with given `seed`,
+//! so same arguments always give same shards, on every platform.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use simd_erasure_core::fixtures;
+//!
+//! let original = fixtures::generate_test_shards(3, 64, 123);
+//! let recovery = simd_erasure_core::encode(3, 2, &original)?;
+//!
+//! assert_eq!(original, fixtures::generate_test_shards(3, 64, 123));
+//! assert_eq!(recovery.len(), 2);
+//! # Ok::<(), simd_erasure_core::Error>(())
+//! ```
+
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
+use rand_chacha::{
+    rand_core::{RngCore, SeedableRng},
+    ChaCha8Rng,
+};
+
+// ======================================================================
+// FUNCTIONS - PUBLIC
+
+/// Returns `shard_count` shards of `shard_bytes` bytes each,
+/// filled with pseudo-random data generated from `seed`.
+///
+/// # Examples
+///
+/// ```rust
+/// use simd_erasure_core::fixtures;
+///
+/// let shards = fixtures::generate_test_shards(4, 100, 1);
+///
+/// assert_eq!(shards.len(), 4);
+/// assert!(shards.iter().all(|shard| shard.len() == 100));
+/// assert_ne!(shards, fixtures::generate_test_shards(4, 100, 2));
+/// ```
+pub fn generate_test_shards(shard_count: usize, shard_bytes: usize, seed: u64) -> Vec<Vec<u8>> {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let mut shards = vec![vec![0u8; shard_bytes]; shard_count];
+    for shard in &mut shards {
+        rng.fill_bytes(shard);
+    }
+    shards
+}
+
+/// Returns `shard_count` shards of `chunk_count` 64-byte chunks each,
+/// filled with pseudo-random data generated from `seed`.
+///
+/// This is the layout used by [`ShardsRefMut`] and [`Engine`],
+/// e.g. for testing custom engines.
+///
+/// Shards have same contents as [`generate_test_shards`]
+/// with same `seed` and `shard_bytes == 64 * chunk_count`.
+///
+/// [`ShardsRefMut`]: crate::engine::ShardsRefMut
+/// [`Engine`]: crate::engine::Engine
+pub fn generate_test_shards_64(
+    shard_count: usize,
+    chunk_count: usize,
+    seed: u64,
+) -> Vec<Vec<[u8; 64]>> {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let mut shards = vec![vec![[0u8; 64]; chunk_count]; shard_count];
+    for shard in &mut shards {
+        rng.fill_bytes(shard.as_flattened_mut());
+    }
+    shards
+}
+
+// ======================================================================
+// TESTS
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_test_shards_64_matches_generate_test_shards() {
+        let shards = generate_test_shards(5, 192, 7);
+        let shards_64 = generate_test_shards_64(5, 3, 7);
+
+        for (shard, shard_64) in shards.iter().zip(&shards_64) {
+            assert_eq!(shard.as_slice(), shard_64.as_flattened());
+        }
+    }
+
+    #[test]
+    fn generate_test_shards_is_deterministic() {
+        assert_eq!(
+            generate_test_shards(3, 10, 1),
+            generate_test_shards(3, 10, 1)
+        );
+        assert_ne!(
+            generate_test_shards(3, 10, 1),
+            generate_test_shards(3, 10, 2)
+        );
+    }
+}
@@ -0,0 +1,238 @@
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use crate::{
+    engine::DefaultEngine,
+    rate::{LowRateEncoder, RateEncoder},
+    Error,
+};
+
+// ======================================================================
+// IncrementalEncoder - PUBLIC
+
+/// Encoder which generates more recovery shards on demand,
+/// e.g. to add redundancy as network conditions worsen.
+///
+/// Recovery shards generated earlier stay valid when more are added,
+/// so after `n` recovery shards in total they are exactly the same
+/// as encoding with `recovery_count == n` in one go.
+///
+/// This always uses [`LowRateEncoder`], because only with low rate
+/// recovery shard `i` doesn't depend on `recovery_count`.
+/// So shards must be decoded with [`LowRateDecoder`] configured with
+/// total number of recovery shards generated so far.
+///
+/// Original shards are copied when encoder is created and each
+/// [`add_recovery_level`] currently re-encodes all recovery shards.
+/// Low rate encoding is one IFFT of original shards followed by
+/// independent FFT of each chunk of recovery shards, so keeping
+/// the IFFT result would allow computing only new chunks instead.
+///
+/// # Examples
+///
+/// ```rust
+/// use simd_erasure_core::{
+///     engine::DefaultEngine,
+///     rate::{LowRateDecoder, RateDecoder},
+///     IncrementalEncoder,
+/// };
+///
+/// let original = [[1u8; 64], [2; 64], [3; 64]];
+/// let mut encoder = IncrementalEncoder::new(3, 64, &original)?;
+///
+/// let first = encoder.add_recovery_level(1)?.to_vec();
+/// let more = encoder.add_recovery_level(2)?.to_vec();
+/// assert_eq!(encoder.recovery_count(), 3);
+///
+/// // Recovery shards of both levels are decoded together.
+/// let mut decoder = LowRateDecoder::new(3, 3, 64, DefaultEngine::new(), None)?;
+/// decoder.add_original_shard(0, original[0])?;
+/// decoder.add_recovery_shard(0, &first[0])?;
+/// decoder.add_recovery_shard(2, &more[1])?;
+///
+/// let result = decoder.decode()?;
+/// assert_eq!(result.restored_original(1), Some(&original[1][..]));
+/// assert_eq!(result.restored_original(2), Some(&original[2][..]));
+/// # Ok::<(), simd_erasure_core::Error>(())
+/// ```
+///
+/// [`LowRateDecoder`]: crate::LowRateDecoder
+/// [`add_recovery_level`]: IncrementalEncoder::add_recovery_level
+#[derive(Debug)]
+pub struct IncrementalEncoder {
+    encoder: LowRateEncoder<DefaultEngine>,
+    original: Vec<u8>,
+    original_count: usize,
+    shard_bytes: usize,
+    recovery: Vec<Vec<u8>>,
+}
+
+impl IncrementalEncoder {
+    /// Generates `additional_recovery` more recovery shards,
+    /// returning only these new shards.
+    ///
+    /// New shards have indexes `recovery_count..recovery_count + additional_recovery`
+    /// where `recovery_count` is the value before this call.
+    ///
+    /// If total number of recovery shards wouldn't be supported,
+    /// returns [`Error::UnsupportedShardCount`] and nothing is generated.
+    pub fn add_recovery_level(&mut self, additional_recovery: usize) -> Result<&[Vec<u8>], Error> {
+        let old_count = self.recovery.len();
+        if additional_recovery == 0 {
+            return Ok(&self.recovery[old_count..]);
+        }
+
+        let recovery_count = old_count.saturating_add(additional_recovery);
+        self.encoder
+            .reset(self.original_count, recovery_count, self.shard_bytes)?;
+
+        for original in self.original.chunks_exact(self.shard_bytes) {
+            self.encoder.add_original_shard(original)?;
+        }
+
+        let result = self.encoder.encode()?;
+        self.recovery
+            .extend(result.recovery_iter().skip(old_count).map(<[u8]>::to_vec));
+
+        Ok(&self.recovery[old_count..])
+    }
+
+    /// Creates new encoder with given original shards.
+    ///
+    /// No recovery shards are generated until
+    /// [`add_recovery_level`](IncrementalEncoder::add_recovery_level) is called.
+    pub fn new<T: AsRef<[u8]>>(
+        original_count: usize,
+        shard_bytes: usize,
+        original: &[T],
+    ) -> Result<Self, Error> {
+        let mut encoder =
+            LowRateEncoder::new(original_count, 1, shard_bytes, DefaultEngine::new(), None)?;
+
+        for original in original {
+            encoder.add_original_shard(original)?;
+        }
+
+        if original.len() < original_count {
+            return Err(Error::TooFewOriginalShards {
+                original_count,
+                original_received_count: original.len(),
+            });
+        }
+
+        Ok(Self {
+            encoder,
+            original: original.iter().flat_map(AsRef::as_ref).copied().collect(),
+            original_count,
+            shard_bytes,
+            recovery: Vec::new(),
+        })
+    }
+
+    /// Returns configured number of original shards.
+    pub fn original_count(&self) -> usize {
+        self.original_count
+    }
+
+    /// Returns all recovery shards generated so far, ordered by their indexes.
+    pub fn recovery(&self) -> &[Vec<u8>] {
+        &self.recovery
+    }
+
+    /// Returns number of recovery shards generated so far.
+    pub fn recovery_count(&self) -> usize {
+        self.recovery.len()
+    }
+
+    /// Returns configured shard size in bytes.
+    pub fn shard_bytes(&self) -> usize {
+        self.shard_bytes
+    }
+}
+
+// ======================================================================
+// TESTS
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        rate::{LowRateDecoder, RateDecoder},
+        test_util,
+    };
+
+    #[test]
+    fn levels_match_encoding_in_one_go() {
+        let original = test_util::generate_original(2, 1024, 123);
+        let mut encoder = IncrementalEncoder::new(2, 1024, &original).unwrap();
+
+        assert_eq!(encoder.add_recovery_level(1).unwrap().len(), 1);
+        assert_eq!(encoder.add_recovery_level(0).unwrap().len(), 0);
+        assert_eq!(encoder.add_recovery_level(2).unwrap().len(), 2);
+        assert_eq!(encoder.recovery_count(), 3);
+        test_util::assert_hash(encoder.recovery(), test_util::LOW_2_3);
+
+        let original = test_util::generate_original(2, 1024, 125);
+        let mut encoder = IncrementalEncoder::new(2, 1024, &original).unwrap();
+
+        let first = encoder.add_recovery_level(3).unwrap().to_vec();
+        let more = encoder.add_recovery_level(2).unwrap().to_vec();
+        assert_eq!(encoder.recovery(), [first, more].concat());
+        test_util::assert_hash(encoder.recovery(), test_util::LOW_2_5);
+    }
+
+    #[test]
+    fn decode_shards_from_different_levels() {
+        let original = test_util::generate_original(3, 1024, 135);
+        let mut encoder = IncrementalEncoder::new(3, 1024, &original).unwrap();
+
+        encoder.add_recovery_level(2).unwrap();
+        encoder.add_recovery_level(3).unwrap();
+
+        let mut decoder = LowRateDecoder::new(3, 5, 1024, DefaultEngine::new(), None).unwrap();
+        decoder
+            .add_recovery_shard(1, &encoder.recovery()[1])
+            .unwrap();
+        decoder
+            .add_recovery_shard(3, &encoder.recovery()[3])
+            .unwrap();
+        decoder
+            .add_recovery_shard(4, &encoder.recovery()[4])
+            .unwrap();
+
+        let result = decoder.decode().unwrap();
+        for (index, original) in original.iter().enumerate() {
+            assert_eq!(result.restored_original(index), Some(&original[..]));
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let original = test_util::generate_original(3, 64, 123);
+
+        assert_eq!(
+            IncrementalEncoder::new(3, 64, &original[..2]).err(),
+            Some(Error::TooFewOriginalShards {
+                original_count: 3,
+                original_received_count: 2,
+            })
+        );
+
+        assert_eq!(
+            IncrementalEncoder::new(2, 64, &original).err(),
+            Some(Error::TooManyOriginalShards { original_count: 2 })
+        );
+
+        let mut encoder = IncrementalEncoder::new(3, 64, &original).unwrap();
+        encoder.add_recovery_level(1).unwrap();
+
+        assert_eq!(
+            encoder.add_recovery_level(65535),
+            Err(Error::UnsupportedShardCount {
+                original_count: 3,
+                recovery_count: 65536,
+            })
+        );
+        assert_eq!(encoder.recovery_count(), 1);
+    }
+}
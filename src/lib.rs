@@ -17,12 +17,22 @@ extern crate alloc;
 
 use alloc::collections::BTreeMap;
 #[cfg(not(feature = "std"))]
-use alloc::vec::Vec;
-use core::fmt;
+use alloc::{string::String, vec::Vec};
+use core::{fmt, ops::Range};
+
+use fixedbitset::FixedBitSet;
+
+use crate::{engine::DefaultEngine, rate::RateEncoder};
 
 pub use crate::{
     decoder_result::{DecoderResult, RestoredOriginal},
     encoder_result::{EncoderResult, Recovery},
+    engine::{GfField, GfPolynomial},
+    incremental_encoder::IncrementalEncoder,
+    rate::{
+        DefaultRateDecoder, DefaultRateEncoder, HighRateDecoder, HighRateEncoder, LowRateDecoder,
+        LowRateEncoder,
+    },
     reed_solomon::{ReedSolomonDecoder, ReedSolomonEncoder},
 };
 
@@ -32,18 +42,69 @@ mod test_util;
 
 mod decoder_result;
 mod encoder_result;
+mod incremental_encoder;
 mod reed_solomon;
 
 pub mod algorithm {
     #![doc = include_str!("algorithm.md")]
 }
+pub mod checksum;
 pub mod engine;
+#[cfg(any(test, feature = "fixtures"))]
+pub mod fixtures;
 pub mod rate;
+pub mod wire;
+
+// ======================================================================
+// CONST - PUBLIC
+
+/// Maximum `original_count`, possible only with `recovery_count == 1`.
+///
+/// See [`is_valid_combination`] for details.
+pub const MAX_ORIGINAL_COUNT: usize = engine::GF_ORDER - 1;
+
+/// Maximum `recovery_count`, possible only with `original_count == 1`.
+///
+/// See [`is_valid_combination`] for details.
+pub const MAX_RECOVERY_COUNT: usize = engine::GF_ORDER - 1;
+
+/// Maximum `original_count + recovery_count`.
+///
+/// This is reached e.g. with [`MAX_ORIGINAL_COUNT`] original shards
+/// and one recovery shard. See [`is_valid_combination`] for details.
+pub const MAX_TOTAL_COUNT: usize = engine::GF_ORDER;
 
 // ======================================================================
 // Error - PUBLIC
 
 /// Represents all possible errors that can occur in this library.
+///
+/// [`Error`] implements [`core::error::Error`], also without `std`,
+/// so it works with `?` in functions returning e.g. `Box<dyn Error>`.
+///
+/// All variants are caused by how this library is used, i.e. by given
+/// configuration, shards or order of calls, so there is no separate
+/// class of internal errors. Internal invariants are checked with
+/// assertions instead. Of these only [`Error::NotEnoughShards`] and
+/// [`Error::TooFewOriginalShards`] can go away by adding more shards,
+/// others need different configuration, shard or order of calls.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::error::Error;
+///
+/// fn recover(recovery: &[u8]) -> Result<(), Box<dyn Error>> {
+///     simd_erasure_core::decode(2, 1, [(0, [0u8; 64]); 0], [(0, recovery)])?;
+///     Ok(())
+/// }
+///
+/// let err = recover(&[0; 64]).unwrap_err();
+/// assert_eq!(
+///     err.to_string(),
+///     "not enough shards: need 2 but received 0 original and 1 recovery"
+/// );
+/// ```
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Error {
     /// Given shard has different size than given or inferred shard size.
@@ -91,6 +152,14 @@ pub enum Error {
         index: usize,
     },
 
+    /// Given `original_count` or `recovery_count` is zero.
+    InvalidShardCount {
+        /// Given number of original shards.
+        original_count: usize,
+        /// Given number of recovery shards.
+        recovery_count: usize,
+    },
+
     /// Given or inferred shard size is invalid:
     /// Size must be non-zero and even.
     ///
@@ -105,6 +174,20 @@ pub enum Error {
         shard_bytes: usize,
     },
 
+    /// Encoder was used after encoding without being reset first.
+    ///
+    /// This happens if [`EncoderResult`] is leaked instead of dropped,
+    /// e.g. with [`core::mem::forget`], and then more original shards
+    /// are added or `encode` is called again.
+    /// Calling `reset` makes the encoder usable again.
+    InvalidState,
+
+    /// Encoder or decoder was used before being configured.
+    ///
+    /// This happens if [`ReedSolomonEncoder`] or [`ReedSolomonDecoder`]
+    /// created with [`Default`] is used before calling `reset`.
+    NotConfigured,
+
     /// Decoder was given too few shards.
     ///
     /// Decoding requires as many shards as there were original shards
@@ -141,6 +224,107 @@ pub enum Error {
     },
 }
 
+impl Error {
+    /// Wraps this error into [`ContextError`] with given `message`
+    /// describing what was being done when the error occurred.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simd_erasure_core::{Error, ReedSolomonEncoder};
+    ///
+    /// let mut encoder = ReedSolomonEncoder::new(2, 1, 64)?;
+    ///
+    /// let err = encoder
+    ///     .add_original_shard([0; 32])
+    ///     .map_err(|e| e.context("while adding shard from peer 7"))
+    ///     .unwrap_err();
+    ///
+    /// assert_eq!(err.to_string(), "while adding shard from peer 7");
+    /// assert!(matches!(err.error(), Error::DifferentShardSize { .. }));
+    /// # Ok::<(), simd_erasure_core::Error>(())
+    /// ```
+    pub fn context(self, message: impl Into<String>) -> ContextError {
+        ContextError {
+            error: self,
+            message: message.into(),
+        }
+    }
+
+    /// Returns indexes of original shards which encoder is still missing
+    /// if this is [`Error::TooFewOriginalShards`], `None` otherwise.
+    ///
+    /// Encoders receive original shards in order,
+    /// so missing shards are always `original_received_count..original_count`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simd_erasure_core::ReedSolomonEncoder;
+    ///
+    /// let mut encoder = ReedSolomonEncoder::new(4, 1, 64)?;
+    /// encoder.add_original_shard([0; 64])?;
+    ///
+    /// let err = encoder.encode().err().unwrap();
+    /// assert_eq!(err.missing_original_indexes(), Some(1..4));
+    /// # Ok::<(), simd_erasure_core::Error>(())
+    /// ```
+    pub fn missing_original_indexes(&self) -> Option<Range<usize>> {
+        match *self {
+            Self::TooFewOriginalShards {
+                original_count,
+                original_received_count,
+            } => Some(original_received_count..original_count),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if this error is caused by how this library is used,
+    /// i.e. by given configuration, shards or order of calls.
+    ///
+    /// Currently this is `true` for all variants, see [`Error`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simd_erasure_core::ReedSolomonEncoder;
+    ///
+    /// let mut encoder = ReedSolomonEncoder::new(2, 1, 64)?;
+    ///
+    /// let err = encoder.add_original_shard([0; 32]).unwrap_err();
+    /// assert!(err.is_usage_error());
+    /// assert!(!err.is_internal_error());
+    /// # Ok::<(), simd_erasure_core::Error>(())
+    /// ```
+    pub fn is_usage_error(&self) -> bool {
+        // No wildcard so that adding a variant
+        // forces deciding which class it belongs to.
+        match self {
+            Self::DifferentShardSize { .. }
+            | Self::DuplicateOriginalShardIndex { .. }
+            | Self::DuplicateRecoveryShardIndex { .. }
+            | Self::InvalidOriginalShardIndex { .. }
+            | Self::InvalidRecoveryShardIndex { .. }
+            | Self::InvalidShardCount { .. }
+            | Self::InvalidShardSize { .. }
+            | Self::InvalidState
+            | Self::NotConfigured
+            | Self::NotEnoughShards { .. }
+            | Self::TooFewOriginalShards { .. }
+            | Self::TooManyOriginalShards { .. }
+            | Self::UnsupportedShardCount { .. } => true,
+        }
+    }
+
+    /// Returns `true` if this error is caused by a bug in this library.
+    ///
+    /// This is the opposite of [`Error::is_usage_error`]
+    /// and currently `false` for all variants, see [`Error`].
+    pub fn is_internal_error(&self) -> bool {
+        !self.is_usage_error()
+    }
+}
+
 // ======================================================================
 // Error - IMPL DISPLAY
 
@@ -182,6 +366,16 @@ impl fmt::Display for Error {
                 )
             }
 
+            Self::InvalidShardCount {
+                original_count,
+                recovery_count,
+            } => {
+                write!(
+                    f,
+                    "invalid shard count: {original_count} original shards with {recovery_count} recovery shards (both must be non-zero)"
+                )
+            }
+
             Self::InvalidShardSize { shard_bytes } => {
                 write!(
                     f,
@@ -189,6 +383,20 @@ impl fmt::Display for Error {
                 )
             }
 
+            Self::InvalidState => {
+                write!(
+                    f,
+                    "invalid state: encoder must be reset before it is used again"
+                )
+            }
+
+            Self::NotConfigured => {
+                write!(
+                    f,
+                    "not configured: encoder/decoder must be reset with a configuration before it is used"
+                )
+            }
+
             Self::NotEnoughShards {
                 original_count,
                 original_received_count,
@@ -196,7 +404,7 @@ impl fmt::Display for Error {
             } => {
                 write!(
                     f,
-                    "not enough shards: {original_received_count} original + {recovery_received_count} recovery < {original_count} original_count",
+                    "not enough shards: need {original_count} but received {original_received_count} original and {recovery_received_count} recovery",
                 )
             }
 
@@ -233,7 +441,72 @@ impl fmt::Display for Error {
 // ======================================================================
 // Error - IMPL ERROR
 
-impl core::error::Error for Error {}
+impl core::error::Error for Error {
+    // All variants are leaf errors.
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        None
+    }
+}
+
+// ======================================================================
+// ContextError - PUBLIC
+
+/// [`Error`] annotated with a context message.
+///
+/// This struct is created by [`Error::context`].
+///
+/// [`Display`] shows only the context message,
+/// the original [`Error`] is available via [`source`].
+///
+/// [`Display`]: fmt::Display
+/// [`source`]: core::error::Error::source
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContextError {
+    error: Error,
+    message: String,
+}
+
+impl ContextError {
+    /// Returns the original [`Error`].
+    pub fn error(&self) -> Error {
+        self.error
+    }
+
+    /// Returns the context message.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+// ======================================================================
+// ContextError - IMPL DISPLAY
+
+impl fmt::Display for ContextError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+// ======================================================================
+// ContextError - IMPL ERROR
+
+impl core::error::Error for ContextError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+// ======================================================================
+// ShardKind - PUBLIC
+
+/// Kind of shard, either original or recovery.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ShardKind {
+    /// Original shard.
+    Original,
+    /// Recovery shard.
+    Recovery,
+}
 
 // ======================================================================
 // FUNCTIONS - PUBLIC
@@ -246,6 +519,8 @@ impl core::error::Error for Error {}
 /// - Recovery shards have indexes `0..recovery_count`
 ///   corresponding to their position in the returned `Vec`.
 /// - These same indexes must be used when decoding.
+/// - `original` can be any collection of shards, e.g. `&[&[u8]]`,
+///   `&[Vec<u8>]`, `&[Box<[u8]>]` or an array of arrays.
 ///
 /// See [simple usage](crate#simple-usage) for an example.
 pub fn encode<T>(
@@ -257,7 +532,12 @@ where
     T: IntoIterator,
     T::Item: AsRef<[u8]>,
 {
-    if !ReedSolomonEncoder::supports(original_count, recovery_count) {
+    if original_count == 0 || recovery_count == 0 {
+        return Err(Error::InvalidShardCount {
+            original_count,
+            recovery_count,
+        });
+    } else if !ReedSolomonEncoder::supports(original_count, recovery_count) {
         return Err(Error::UnsupportedShardCount {
             original_count,
             recovery_count,
@@ -287,6 +567,237 @@ where
     Ok(result.recovery_iter().map(<[u8]>::to_vec).collect())
 }
 
+/// Encodes in one go, returning only recovery shards in `recovery_range`.
+///
+/// This allows several nodes to share generating recovery shards:
+/// Each node is given all original shards and generates its own range.
+/// Returned shards are the same as `encode(..)?[recovery_range]` would give,
+/// so they have indexes `recovery_range` when decoding.
+///
+/// Note that this doesn't do less work than [`encode`],
+/// all recovery shards are generated and only requested ones are copied out.
+///
+/// # Errors
+///
+/// If `recovery_range` isn't within `0..recovery_count`,
+/// returns [`Error::InvalidRecoveryShardIndex`] with `index` being
+/// `recovery_range.end - 1` if that is too large,
+/// or `recovery_range.start` if it is past `recovery_range.end`.
+///
+/// # Examples
+///
+/// ```rust
+/// let original = [[1u8; 64], [2; 64], [3; 64]];
+///
+/// // Two nodes generating 4 recovery shards together ...
+/// let first = simd_erasure_core::split_encode(3, 4, 64, &original, 0..2)?;
+/// let second = simd_erasure_core::split_encode(3, 4, 64, &original, 2..4)?;
+///
+/// // ... give same shards as generating them all at once.
+/// let all = simd_erasure_core::encode(3, 4, &original)?;
+/// assert_eq!([first, second].concat(), all);
+/// # Ok::<(), simd_erasure_core::Error>(())
+/// ```
+pub fn split_encode<T: AsRef<[u8]>>(
+    original_count: usize,
+    recovery_count: usize,
+    shard_bytes: usize,
+    original: &[T],
+    recovery_range: Range<usize>,
+) -> Result<Vec<Vec<u8>>, Error> {
+    if recovery_range.end > recovery_count {
+        return Err(Error::InvalidRecoveryShardIndex {
+            recovery_count,
+            index: recovery_range.end - 1,
+        });
+    }
+
+    if recovery_range.start > recovery_range.end {
+        return Err(Error::InvalidRecoveryShardIndex {
+            recovery_count,
+            index: recovery_range.start,
+        });
+    }
+
+    let mut encoder = ReedSolomonEncoder::new(original_count, recovery_count, shard_bytes)?;
+
+    for original in original {
+        encoder.add_original_shard(original)?;
+    }
+
+    let result = encoder.encode()?;
+
+    Ok(result
+        .recovery_iter()
+        .take(recovery_range.end)
+        .skip(recovery_range.start)
+        .map(<[u8]>::to_vec)
+        .collect())
+}
+
+/// Encodes same original shards with several recovery counts,
+/// returning one set of recovery shards per recovery count.
+///
+/// Each returned set is the same as
+/// `encode(original_count, recovery_count, original)?` would give,
+/// e.g. for tiered storage with different amounts of redundancy.
+///
+/// Recovery counts for which [`ReedSolomonEncoder`] uses low rate
+/// share all work: With low rate recovery shard `i` doesn't depend on
+/// `recovery_count`, so these are all taken from one encoding
+/// with the largest of them. Recovery counts which use high rate
+/// are encoded separately, because there the transforms
+/// depend on `recovery_count`.
+///
+/// # Examples
+///
+/// ```rust
+/// let original = [[1u8; 64], [2; 64], [3; 64]];
+///
+/// let tiers = simd_erasure_core::multi_encode(3, &[5, 6, 8], 64, &original)?;
+///
+/// assert_eq!(tiers[0], simd_erasure_core::encode(3, 5, &original)?);
+/// assert_eq!(tiers[1], simd_erasure_core::encode(3, 6, &original)?);
+/// assert_eq!(tiers[2], simd_erasure_core::encode(3, 8, &original)?);
+/// # Ok::<(), simd_erasure_core::Error>(())
+/// ```
+pub fn multi_encode<T: AsRef<[u8]>>(
+    original_count: usize,
+    recovery_counts: &[usize],
+    shard_bytes: usize,
+    original: &[T],
+) -> Result<Vec<Vec<Vec<u8>>>, Error> {
+    let mut low_rate_recovery_count = 0;
+    for &recovery_count in recovery_counts {
+        if !rate::use_high_rate(original_count, recovery_count)? {
+            low_rate_recovery_count = low_rate_recovery_count.max(recovery_count);
+        }
+    }
+
+    // LOW RATE - ENCODE ONCE
+
+    let mut low_rate_recovery = Vec::new();
+    if low_rate_recovery_count > 0 {
+        let mut encoder = LowRateEncoder::new(
+            original_count,
+            low_rate_recovery_count,
+            shard_bytes,
+            DefaultEngine::new(),
+            None,
+        )?;
+
+        for original in original {
+            encoder.add_original_shard(original)?;
+        }
+
+        let result = encoder.encode()?;
+        low_rate_recovery = result.recovery_iter().map(<[u8]>::to_vec).collect();
+    }
+
+    // HIGH RATE - ENCODE SEPARATELY
+
+    let mut encoder = ReedSolomonEncoder::default();
+    let mut recovery_sets = Vec::with_capacity(recovery_counts.len());
+
+    for &recovery_count in recovery_counts {
+        if rate::use_high_rate(original_count, recovery_count)? {
+            encoder.reset(original_count, recovery_count, shard_bytes)?;
+            for original in original {
+                encoder.add_original_shard(original)?;
+            }
+
+            let result = encoder.encode()?;
+            recovery_sets.push(result.recovery_iter().map(<[u8]>::to_vec).collect());
+        } else {
+            recovery_sets.push(low_rate_recovery[..recovery_count].to_vec());
+        }
+    }
+
+    Ok(recovery_sets)
+}
+
+/// Encodes `original_count` original shards of `shard_bytes` bytes
+/// read from `reader`, returning generated recovery shards.
+///
+/// Exactly `original_count * shard_bytes` bytes are read,
+/// so `reader` can continue to be used e.g. for the next batch of shards.
+/// Only one original shard is buffered at a time
+/// in addition to working space of [`ReedSolomonEncoder`].
+///
+/// Any [`Read`] works, e.g. a file wrapped in [`BufReader`].
+/// Shards are read with [`Read::read_exact`] into own buffer,
+/// so requiring [`BufRead`] would gain nothing.
+///
+/// # Errors
+///
+/// Errors are [`std::io::Error`] since reading can fail in ways
+/// which [`Error`] can't represent, it being `Copy` and `PartialEq`.
+/// Errors of this library can be recovered with
+/// `err.get_ref().and_then(|e| e.downcast_ref::<Error>())`.
+///
+/// - If `reader` ends before all original shards have been read,
+///   returns [`ErrorKind::UnexpectedEof`] error
+///   wrapping [`Error::TooFewOriginalShards`].
+/// - Other errors from `reader` are returned as-is.
+/// - Errors of this library, e.g. [`Error::InvalidShardSize`],
+///   are returned as [`ErrorKind::InvalidInput`] error wrapping the [`Error`].
+///
+/// # Examples
+///
+/// ```rust
+/// use std::io::Cursor;
+///
+/// let data = vec![1u8; 3 * 64];
+///
+/// let recovery = simd_erasure_core::encode_buffered(3, 2, 64, Cursor::new(&data))?;
+/// assert_eq!(recovery, simd_erasure_core::encode(3, 2, data.chunks(64))?);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// [`Read`]: std::io::Read
+/// [`Read::read_exact`]: std::io::Read::read_exact
+/// [`BufRead`]: std::io::BufRead
+/// [`BufReader`]: std::io::BufReader
+/// [`ErrorKind::UnexpectedEof`]: std::io::ErrorKind::UnexpectedEof
+/// [`ErrorKind::InvalidInput`]: std::io::ErrorKind::InvalidInput
+#[cfg(feature = "std")]
+pub fn encode_buffered<R: std::io::Read>(
+    original_count: usize,
+    recovery_count: usize,
+    shard_bytes: usize,
+    mut reader: R,
+) -> std::io::Result<Vec<Vec<u8>>> {
+    use std::io::{self, ErrorKind};
+
+    let invalid_input = |err: Error| io::Error::new(ErrorKind::InvalidInput, err);
+
+    let mut encoder = ReedSolomonEncoder::new(original_count, recovery_count, shard_bytes)
+        .map_err(invalid_input)?;
+
+    let mut shard = vec![0; shard_bytes];
+    for original_received_count in 0..original_count {
+        reader.read_exact(&mut shard).map_err(|err| {
+            if err.kind() == ErrorKind::UnexpectedEof {
+                io::Error::new(
+                    ErrorKind::UnexpectedEof,
+                    Error::TooFewOriginalShards {
+                        original_count,
+                        original_received_count,
+                    },
+                )
+            } else {
+                err
+            }
+        })?;
+
+        encoder.add_original_shard(&shard).map_err(invalid_input)?;
+    }
+
+    let result = encoder.encode().map_err(invalid_input)?;
+
+    Ok(result.recovery_iter().map(<[u8]>::to_vec).collect())
+}
+
 /// Decodes in one go using [`ReedSolomonDecoder`],
 /// returning restored original shards with their indexes.
 ///
@@ -305,7 +816,12 @@ where
     OT: AsRef<[u8]>,
     RT: AsRef<[u8]>,
 {
-    if !ReedSolomonDecoder::supports(original_count, recovery_count) {
+    if original_count == 0 || recovery_count == 0 {
+        return Err(Error::InvalidShardCount {
+            original_count,
+            recovery_count,
+        });
+    } else if !ReedSolomonDecoder::supports(original_count, recovery_count) {
         return Err(Error::UnsupportedShardCount {
             original_count,
             recovery_count,
@@ -352,6 +868,108 @@ where
     Ok(result)
 }
 
+/// Repairs original shards which are known to be corrupt,
+/// returning repaired original shards with their indexes.
+///
+/// This is like [`decode`] but takes all original and recovery shards
+/// together with indexes of those which are known to be corrupt.
+/// Corrupt shards are treated as missing and the rest are used for decoding,
+/// so there must be at least `original_count` uncorrupted shards in total.
+///
+/// - `original` and `recovery` are shards in order of their indexes.
+///   These may be shorter than `original_count` and `recovery_count`
+///   in which case the shards at the end are treated as missing.
+/// - Only original shards which are corrupt or missing are returned.
+///
+/// # Examples
+///
+/// ```rust
+/// let original = [[1u8; 64], [2u8; 64], [3u8; 64]];
+/// let recovery = simd_erasure_core::encode(3, 2, &original)?;
+///
+/// let mut received = original;
+/// received[1][5] ^= 0xff;
+///
+/// let repaired = simd_erasure_core::repair(3, 2, &[1], &[], &received, &recovery)?;
+///
+/// assert_eq!(repaired.len(), 1);
+/// assert_eq!(repaired[&1], original[1]);
+/// # Ok::<(), simd_erasure_core::Error>(())
+/// ```
+pub fn repair<O, R>(
+    original_count: usize,
+    recovery_count: usize,
+    corrupt_original_indexes: &[usize],
+    corrupt_recovery_indexes: &[usize],
+    original: &[O],
+    recovery: &[R],
+) -> Result<BTreeMap<usize, Vec<u8>>, Error>
+where
+    O: AsRef<[u8]>,
+    R: AsRef<[u8]>,
+{
+    let mut original_corrupt = FixedBitSet::with_capacity(original_count);
+    for &index in corrupt_original_indexes {
+        if index >= original_count {
+            return Err(Error::InvalidOriginalShardIndex {
+                original_count,
+                index,
+            });
+        }
+        original_corrupt.insert(index);
+    }
+
+    let mut recovery_corrupt = FixedBitSet::with_capacity(recovery_count);
+    for &index in corrupt_recovery_indexes {
+        if index >= recovery_count {
+            return Err(Error::InvalidRecoveryShardIndex {
+                recovery_count,
+                index,
+            });
+        }
+        recovery_corrupt.insert(index);
+    }
+
+    decode(
+        original_count,
+        recovery_count,
+        original
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !original_corrupt.contains(*index)),
+        recovery
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !recovery_corrupt.contains(*index)),
+    )
+}
+
+/// Returns `true` if given shard counts are supported,
+/// i.e. if [`ReedSolomonEncoder`] and [`ReedSolomonDecoder`]
+/// can be created with them.
+///
+/// Any combination of 1 - 32768 original shards with 1 - 32768
+/// recovery shards is supported. Larger counts up to
+/// [`MAX_ORIGINAL_COUNT`] and [`MAX_RECOVERY_COUNT`] are supported
+/// if the smaller count, rounded up to a power of two,
+/// plus the larger count is at most [`MAX_TOTAL_COUNT`].
+///
+/// This is same as [`ReedSolomonEncoder::supports`].
+///
+/// # Examples
+///
+/// ```rust
+/// use simd_erasure_core::{is_valid_combination, MAX_ORIGINAL_COUNT};
+///
+/// assert!(is_valid_combination(32768, 32768));
+/// assert!(is_valid_combination(MAX_ORIGINAL_COUNT, 1));
+/// assert!(!is_valid_combination(MAX_ORIGINAL_COUNT, 2));
+/// assert!(!is_valid_combination(0, 1));
+/// ```
+pub fn is_valid_combination(original_count: usize, recovery_count: usize) -> bool {
+    ReedSolomonEncoder::supports(original_count, recovery_count)
+}
+
 // ======================================================================
 // TESTS
 
@@ -378,6 +996,156 @@ mod tests {
         assert_eq!(restored[&1], original[1]);
     }
 
+    // ============================================================
+    // encode_buffered
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn encode_buffered() {
+        use std::io::{Cursor, ErrorKind, Read};
+
+        let original = test_util::generate_original(3, 1024, 123);
+        let expected = encode(3, 2, &original).unwrap();
+
+        // Extra data after original shards is left unread.
+        let mut data = original.concat();
+        data.extend_from_slice(b"next");
+        let mut reader = Cursor::new(&data);
+
+        assert_eq!(
+            super::encode_buffered(3, 2, 1024, &mut reader).unwrap(),
+            expected
+        );
+        let mut rest = Vec::new();
+        reader.read_to_end(&mut rest).unwrap();
+        assert_eq!(rest, b"next");
+
+        // Too short input.
+        let err = super::encode_buffered(3, 2, 1024, Cursor::new(&data[..2500])).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+        assert_eq!(
+            err.get_ref().unwrap().downcast_ref::<Error>(),
+            Some(&Error::TooFewOriginalShards {
+                original_count: 3,
+                original_received_count: 2,
+            })
+        );
+
+        // Invalid configuration.
+        let err = super::encode_buffered(3, 2, 1023, Cursor::new(&data)).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert_eq!(
+            err.get_ref().unwrap().downcast_ref::<Error>(),
+            Some(&Error::InvalidShardSize { shard_bytes: 1023 })
+        );
+    }
+
+    // ============================================================
+    // split_encode
+
+    #[test]
+    fn split_encode() {
+        let original = test_util::generate_original(3, 1024, 123);
+        let recovery = encode(3, 5, &original).unwrap();
+
+        for range in [0..5, 0..2, 2..5, 4..5, 3..3] {
+            assert_eq!(
+                super::split_encode(3, 5, 1024, &original, range.clone()).unwrap(),
+                recovery[range]
+            );
+        }
+
+        assert_eq!(
+            super::split_encode(3, 5, 1024, &original, 4..6),
+            Err(Error::InvalidRecoveryShardIndex {
+                recovery_count: 5,
+                index: 5,
+            })
+        );
+
+        #[allow(clippy::reversed_empty_ranges)]
+        let reversed = 3..2;
+        assert_eq!(
+            super::split_encode(3, 5, 1024, &original, reversed),
+            Err(Error::InvalidRecoveryShardIndex {
+                recovery_count: 5,
+                index: 3,
+            })
+        );
+
+        assert_eq!(
+            super::split_encode(3, 5, 1024, &original[..2], 0..5),
+            Err(Error::TooFewOriginalShards {
+                original_count: 3,
+                original_received_count: 2,
+            })
+        );
+    }
+
+    // ============================================================
+    // multi_encode
+
+    #[test]
+    fn multi_encode() {
+        let original = test_util::generate_original(3, 1024, 123);
+
+        // High, low, low, high, low.
+        let recovery_counts = [2, 5, 8, 3, 6];
+        let recovery_sets = super::multi_encode(3, &recovery_counts, 1024, &original).unwrap();
+
+        assert_eq!(recovery_sets.len(), recovery_counts.len());
+        for (recovery_count, recovery) in recovery_counts.iter().zip(&recovery_sets) {
+            assert_eq!(*recovery, encode(3, *recovery_count, &original).unwrap());
+        }
+
+        assert_eq!(super::multi_encode(3, &[], 1024, &original), Ok(Vec::new()));
+
+        assert_eq!(
+            super::multi_encode(3, &[5, 65535], 1024, &original),
+            Err(Error::UnsupportedShardCount {
+                original_count: 3,
+                recovery_count: 65535,
+            })
+        );
+
+        assert_eq!(
+            super::multi_encode(3, &[2, 5], 1024, &original[..2]),
+            Err(Error::TooFewOriginalShards {
+                original_count: 3,
+                original_received_count: 2,
+            })
+        );
+    }
+
+    // ============================================================
+    // MAX COUNTS
+
+    #[test]
+    fn max_counts() {
+        assert!(is_valid_combination(MAX_ORIGINAL_COUNT, 1));
+        assert!(is_valid_combination(1, MAX_RECOVERY_COUNT));
+        assert!(is_valid_combination(
+            MAX_TOTAL_COUNT / 2,
+            MAX_TOTAL_COUNT / 2
+        ));
+
+        assert!(!is_valid_combination(MAX_ORIGINAL_COUNT + 1, 1));
+        assert!(!is_valid_combination(1, MAX_RECOVERY_COUNT + 1));
+        assert!(!is_valid_combination(MAX_ORIGINAL_COUNT, 2));
+        assert!(!is_valid_combination(
+            MAX_TOTAL_COUNT / 2 + 1,
+            MAX_TOTAL_COUNT / 2
+        ));
+
+        let original = alloc::vec![[1u8; 2]; MAX_ORIGINAL_COUNT];
+        assert_eq!(encode(MAX_ORIGINAL_COUNT, 1, &original).unwrap().len(), 1);
+
+        assert_eq!(
+            encode(1, MAX_RECOVERY_COUNT, [[1u8; 2]]).unwrap().len(),
+            MAX_RECOVERY_COUNT
+        );
+    }
+
     // ==================================================
     // trait Send
 
@@ -408,11 +1176,197 @@ mod tests {
         assert_sync::<Error>();
     }
 
+    #[test]
+    fn engines_are_send_sync() {
+        fn require_send_sync<T: engine::Engine + Send + Sync>() {}
+        require_send_sync::<DefaultEngine>();
+        require_send_sync::<engine::Naive>();
+        require_send_sync::<engine::NoSimd>();
+
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            require_send_sync::<engine::Avx2>();
+            require_send_sync::<engine::Ssse3>();
+        }
+
+        #[cfg(target_arch = "aarch64")]
+        require_send_sync::<engine::Neon>();
+    }
+
+    // ============================================================
+    // Error::context
+
+    mod context {
+        use super::*;
+        use alloc::string::ToString;
+        use core::error::Error as _;
+
+        #[test]
+        fn display_and_accessors() {
+            let err = Error::InvalidShardSize { shard_bytes: 3 }.context("while creating encoder");
+
+            assert_eq!(err.to_string(), "while creating encoder");
+            assert_eq!(err.message(), "while creating encoder");
+            assert_eq!(err.error(), Error::InvalidShardSize { shard_bytes: 3 });
+        }
+
+        #[test]
+        fn source_is_original_error() {
+            let err = Error::InvalidState.context(String::from("while encoding"));
+
+            let source = err.source().unwrap();
+            assert_eq!(source.to_string(), Error::InvalidState.to_string());
+            assert_eq!(source.downcast_ref::<Error>(), Some(&Error::InvalidState));
+            assert!(source.source().is_none());
+        }
+
+        #[test]
+        fn from_encoder() {
+            let mut encoder = ReedSolomonEncoder::new(2, 1, 64).unwrap();
+
+            let err = encoder
+                .add_original_shard([0; 32])
+                .map_err(|e| e.context("peer 7"))
+                .unwrap_err();
+
+            assert_eq!(
+                err.error(),
+                Error::DifferentShardSize {
+                    shard_bytes: 64,
+                    got: 32
+                }
+            );
+        }
+    }
+
+    // ============================================================
+    // Error - IMPL DISPLAY / ERROR
+
+    #[test]
+    fn error_display_and_source() {
+        use alloc::string::ToString;
+        use core::error::Error as _;
+
+        let err = Error::NotEnoughShards {
+            original_count: 5,
+            original_received_count: 3,
+            recovery_received_count: 1,
+        };
+
+        assert_eq!(
+            err.to_string(),
+            "not enough shards: need 5 but received 3 original and 1 recovery"
+        );
+        assert!(err.source().is_none());
+    }
+
+    // ============================================================
+    // Error - VARIANTS
+
+    #[test]
+    fn is_usage_error_xor_is_internal_error() {
+        let errors = [
+            Error::DifferentShardSize {
+                shard_bytes: 64,
+                got: 32,
+            },
+            Error::DuplicateOriginalShardIndex { index: 0 },
+            Error::DuplicateRecoveryShardIndex { index: 0 },
+            Error::InvalidOriginalShardIndex {
+                original_count: 2,
+                index: 2,
+            },
+            Error::InvalidRecoveryShardIndex {
+                recovery_count: 2,
+                index: 2,
+            },
+            Error::InvalidShardCount {
+                original_count: 0,
+                recovery_count: 1,
+            },
+            Error::InvalidShardSize { shard_bytes: 3 },
+            Error::InvalidState,
+            Error::NotConfigured,
+            Error::NotEnoughShards {
+                original_count: 2,
+                original_received_count: 0,
+                recovery_received_count: 1,
+            },
+            Error::TooFewOriginalShards {
+                original_count: 2,
+                original_received_count: 1,
+            },
+            Error::TooManyOriginalShards { original_count: 2 },
+            Error::UnsupportedShardCount {
+                original_count: 60000,
+                recovery_count: 60000,
+            },
+        ];
+
+        for err in errors {
+            assert!(err.is_usage_error() != err.is_internal_error(), "{err:?}");
+            assert!(err.is_usage_error(), "{err:?}");
+        }
+    }
+
+    // ============================================================
+    // Error::missing_original_indexes
+
+    #[test]
+    fn missing_original_indexes() {
+        let mut encoder = ReedSolomonEncoder::new(5, 2, 64).unwrap();
+        assert_eq!(
+            encoder.encode().err().unwrap().missing_original_indexes(),
+            Some(0..5)
+        );
+
+        encoder.add_original_shard([0; 64]).unwrap();
+        encoder.add_original_shard([1; 64]).unwrap();
+        assert_eq!(
+            encoder.encode().err().unwrap().missing_original_indexes(),
+            Some(2..5)
+        );
+
+        assert_eq!(
+            Error::InvalidShardSize { shard_bytes: 3 }.missing_original_indexes(),
+            None
+        );
+    }
+
     // ============================================================
     // encode
 
     mod encode {
         use super::super::*;
+        #[cfg(not(feature = "std"))]
+        use alloc::{boxed::Box, vec::Vec};
+
+        // ==================================================
+        // CONTAINER TYPES
+
+        #[test]
+        fn slice_of_slices() {
+            let original = test_util::generate_original(2, 1024, 123);
+            let original: Vec<&[u8]> = original.iter().map(Vec::as_slice).collect();
+            let recovery = encode(2, 3, original.as_slice()).unwrap();
+            test_util::assert_hash(&recovery, test_util::LOW_2_3);
+        }
+
+        #[test]
+        fn slice_of_vecs() {
+            let original = test_util::generate_original(2, 1024, 123);
+            let recovery = encode(2, 3, original.as_slice()).unwrap();
+            test_util::assert_hash(&recovery, test_util::LOW_2_3);
+        }
+
+        #[test]
+        fn slice_of_boxed_slices() {
+            let original = test_util::generate_original(2, 1024, 123);
+            let original: Vec<Box<[u8]>> =
+                original.into_iter().map(Vec::into_boxed_slice).collect();
+            let recovery = encode(2, 3, original.as_slice()).unwrap();
+            test_util::assert_hash(&recovery, test_util::LOW_2_3);
+        }
 
         // ==================================================
         // ERRORS
@@ -456,10 +1410,10 @@ mod tests {
         }
 
         #[test]
-        fn unsupported_shard_count_with_zero_original_count() {
+        fn invalid_shard_count_with_zero_original_count() {
             assert_eq!(
                 encode(0, 1, &[] as &[&[u8]]),
-                Err(Error::UnsupportedShardCount {
+                Err(Error::InvalidShardCount {
                     original_count: 0,
                     recovery_count: 1,
                 })
@@ -467,10 +1421,10 @@ mod tests {
         }
 
         #[test]
-        fn unsupported_shard_count_with_zero_recovery_count() {
+        fn invalid_shard_count_with_zero_recovery_count() {
             assert_eq!(
                 encode(1, 0, &[[0u8; 64]]),
-                Err(Error::UnsupportedShardCount {
+                Err(Error::InvalidShardCount {
                     original_count: 1,
                     recovery_count: 0,
                 })
@@ -595,10 +1549,10 @@ mod tests {
         }
 
         #[test]
-        fn unsupported_shard_count_with_zero_original_count() {
+        fn invalid_shard_count_with_zero_original_count() {
             assert_eq!(
                 decode(0, 1, [(0, ""); 0], [(0, ""); 0]),
-                Err(Error::UnsupportedShardCount {
+                Err(Error::InvalidShardCount {
                     original_count: 0,
                     recovery_count: 1,
                 })
@@ -606,14 +1560,89 @@ mod tests {
         }
 
         #[test]
-        fn unsupported_shard_count_with_zero_recovery_count() {
+        fn invalid_shard_count_with_zero_recovery_count() {
             assert_eq!(
                 decode(1, 0, [(0, ""); 0], [(0, ""); 0]),
-                Err(Error::UnsupportedShardCount {
+                Err(Error::InvalidShardCount {
                     original_count: 1,
                     recovery_count: 0,
                 })
             );
         }
     }
+
+    // ============================================================
+    // repair
+
+    mod repair {
+        use super::super::*;
+
+        #[test]
+        fn corrupt_original_and_recovery() {
+            let original = test_util::generate_original(3, 1024, 0);
+            let recovery = encode(3, 3, &original).unwrap();
+
+            let mut received = original.clone();
+            received[0][10] ^= 1;
+            received[2][20] ^= 1;
+            let mut recovery_received = recovery.clone();
+            recovery_received[1][30] ^= 1;
+
+            let repaired = repair(3, 3, &[0, 2], &[1], &received, &recovery_received).unwrap();
+
+            assert_eq!(repaired.len(), 2);
+            assert_eq!(repaired[&0], original[0]);
+            assert_eq!(repaired[&2], original[2]);
+        }
+
+        #[test]
+        fn missing_shards_at_end() {
+            let original = test_util::generate_original(3, 1024, 0);
+            let recovery = encode(3, 2, &original).unwrap();
+
+            let repaired = repair(3, 2, &[0], &[], &original[..2], &recovery).unwrap();
+
+            assert_eq!(repaired.len(), 2);
+            assert_eq!(repaired[&0], original[0]);
+            assert_eq!(repaired[&2], original[2]);
+        }
+
+        // ==================================================
+        // ERRORS
+
+        #[test]
+        fn invalid_corrupt_index() {
+            let shards = [[0u8; 64]; 2];
+
+            assert_eq!(
+                repair(2, 1, &[2], &[], &shards, &shards[..1]),
+                Err(Error::InvalidOriginalShardIndex {
+                    original_count: 2,
+                    index: 2,
+                })
+            );
+
+            assert_eq!(
+                repair(2, 1, &[], &[1], &shards, &shards[..1]),
+                Err(Error::InvalidRecoveryShardIndex {
+                    recovery_count: 1,
+                    index: 1,
+                })
+            );
+        }
+
+        #[test]
+        fn not_enough_shards() {
+            let shards = [[0u8; 64]; 2];
+
+            assert_eq!(
+                repair(2, 1, &[0], &[0], &shards, &shards[..1]),
+                Err(Error::NotEnoughShards {
+                    original_count: 2,
+                    original_received_count: 1,
+                    recovery_received_count: 0,
+                })
+            );
+        }
+    }
 }
@@ -28,10 +28,13 @@
 //! [`ReedSolomonDecoder`]: crate::ReedSolomonDecoder
 //! [`DefaultEngine`]: crate::engine::DefaultEngine
 
-use crate::{engine::Engine, DecoderResult, EncoderResult, Error};
+use crate::{engine::Engine, DecoderResult, EncoderResult, Error, ShardKind};
+
+pub(crate) use self::rate_default::use_high_rate;
 
 pub use self::{
-    decoder_work::DecoderWork,
+    decoder_work::{DecoderWork, DecoderWorkSnapshot},
+    decoding_timings::DecodingTimings,
     encoder_work::EncoderWork,
     rate_default::{DefaultRate, DefaultRateDecoder, DefaultRateEncoder},
     rate_high::{HighRate, HighRateDecoder, HighRateEncoder},
@@ -39,11 +42,41 @@ pub use self::{
 };
 
 mod decoder_work;
+mod decoding_timings;
 mod encoder_work;
+mod progress;
 mod rate_default;
 mod rate_high;
 mod rate_low;
 
+// ======================================================================
+// FUNCTIONS - PUBLIC
+
+/// Returns number of FFT/IFFT butterfly operations needed to encode
+/// `original_count` original shards into `recovery_count` recovery shards
+/// using [`DefaultRate`], or `0` if given shard counts aren't supported.
+///
+/// This is the `total` reported by [`ReedSolomonEncoder::encode_with_progress`]
+/// so it can be used to estimate encoding time before encoding.
+/// It grows roughly as `(original_count + recovery_count) / 2 * log2(n)`
+/// where `n` is the smaller shard count rounded up to a power of two.
+///
+/// Count is same for all [`Engine`]:s, only time per butterfly differs.
+///
+/// # Examples
+///
+/// ```rust
+/// use simd_erasure_core::rate;
+///
+/// assert_eq!(rate::butterfly_count(1024, 1024), 10240);
+/// assert_eq!(rate::butterfly_count(0, 1024), 0);
+/// ```
+///
+/// [`ReedSolomonEncoder::encode_with_progress`]: crate::ReedSolomonEncoder::encode_with_progress
+pub fn butterfly_count(original_count: usize, recovery_count: usize) -> u64 {
+    rate_default::encode_butterfly_count(original_count, recovery_count).unwrap_or(0) as u64
+}
+
 // ======================================================================
 // Rate - PUBLIC
 
@@ -93,7 +126,12 @@ pub trait Rate<E: Engine> {
         recovery_count: usize,
         shard_bytes: usize,
     ) -> Result<(), Error> {
-        if !Self::supports(original_count, recovery_count) {
+        if original_count == 0 || recovery_count == 0 {
+            Err(Error::InvalidShardCount {
+                original_count,
+                recovery_count,
+            })
+        } else if !Self::supports(original_count, recovery_count) {
             Err(Error::UnsupportedShardCount {
                 original_count,
                 recovery_count,
@@ -126,6 +164,12 @@ where
     /// Like [`ReedSolomonEncoder::encode`](crate::ReedSolomonEncoder::encode).
     fn encode(&mut self) -> Result<EncoderResult<'_>, Error>;
 
+    /// Like [`ReedSolomonEncoder::encode_with_progress`](crate::ReedSolomonEncoder::encode_with_progress).
+    fn encode_with_progress<F: FnMut(usize, usize)>(
+        &mut self,
+        progress: F,
+    ) -> Result<EncoderResult<'_>, Error>;
+
     /// Consumes this encoder returning its [`Engine`] and [`EncoderWork`]
     /// so that they can be re-used by another encoder.
     fn into_parts(self) -> (E, EncoderWork);
@@ -203,6 +247,12 @@ where
     /// Like [`ReedSolomonDecoder::decode`](crate::ReedSolomonDecoder::decode).
     fn decode(&mut self) -> Result<DecoderResult<'_>, Error>;
 
+    /// Like [`ReedSolomonDecoder::decode_with_progress`](crate::ReedSolomonDecoder::decode_with_progress).
+    fn decode_with_progress<F: FnMut(usize, usize)>(
+        &mut self,
+        progress: F,
+    ) -> Result<DecoderResult<'_>, Error>;
+
     /// Consumes this decoder returning its [`Engine`] and [`DecoderWork`]
     /// so that they can be re-used by another decoder.
     fn into_parts(self) -> (E, DecoderWork);
@@ -228,6 +278,19 @@ where
     // ============================================================
     // PROVIDED
 
+    /// Like [`ReedSolomonDecoder::add_shard`](crate::ReedSolomonDecoder::add_shard).
+    fn add_shard<T: AsRef<[u8]>>(
+        &mut self,
+        kind: ShardKind,
+        index: usize,
+        shard: T,
+    ) -> Result<(), Error> {
+        match kind {
+            ShardKind::Original => self.add_original_shard(index, shard),
+            ShardKind::Recovery => self.add_recovery_shard(index, shard),
+        }
+    }
+
     /// Returns `true` if given `original_count` / `recovery_count`
     /// combination is supported.
     ///
@@ -1,8 +1,13 @@
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use core::fmt;
+
 use fixedbitset::FixedBitSet;
 
 use crate::{
     engine::{Shards, ShardsRefMut},
-    Error,
+    rate::DecodingTimings,
+    Error, ShardKind,
 };
 
 // ======================================================================
@@ -10,7 +15,17 @@ use crate::{
 
 /// Working space for [`RateDecoder`].
 ///
+/// All allocations happen when working space is created or reset
+/// to a larger configuration, after that decoding rounds don't allocate.
+///
+/// Working space has [`HighRate::decoder_work_count`] or
+/// [`LowRate::decoder_work_count`] shards
+/// for given configuration, each taking `shard_bytes` rounded up
+/// to a multiple of 64 bytes, e.g. for sizing memory budgets.
+///
 /// [`RateDecoder`]: crate::rate::RateDecoder
+/// [`HighRate::decoder_work_count`]: crate::rate::HighRate::decoder_work_count
+/// [`LowRate::decoder_work_count`]: crate::rate::LowRate::decoder_work_count
 pub struct DecoderWork {
     original_count: usize,
     recovery_count: usize,
@@ -24,6 +39,9 @@ pub struct DecoderWork {
     // May contain extra zero bits.
     received: FixedBitSet,
     shards: Shards,
+
+    #[cfg(feature = "timing")]
+    decode_timings: Option<DecodingTimings>,
 }
 
 impl DecoderWork {
@@ -42,10 +60,144 @@ impl DecoderWork {
             recovery_received_count: 0,
             received: FixedBitSet::new(),
             shards: Shards::new(),
+
+            #[cfg(feature = "timing")]
+            decode_timings: None,
+        }
+    }
+
+    /// Returns time spent in each step of the latest decoding.
+    ///
+    /// Returns `None` if `timing` feature isn't enabled, or if
+    /// there hasn't been any decoding since this [`DecoderWork`]
+    /// was created or reset to a new configuration,
+    /// or if latest decoding had no missing original shards to restore.
+    pub fn decode_timings(&self) -> Option<DecodingTimings> {
+        #[cfg(feature = "timing")]
+        return self.decode_timings;
+        #[cfg(not(feature = "timing"))]
+        None
+    }
+
+    /// Returns iterator over received shards as `(kind, index)` pairs,
+    /// first original shards and then recovery shards,
+    /// both ordered by their indexes.
+    pub fn iter_received(&self) -> impl Iterator<Item = (ShardKind, usize)> + '_ {
+        let original = (0..self.original_count)
+            .filter(|index| self.received[self.original_base_pos + index])
+            .map(|index| (ShardKind::Original, index));
+
+        let recovery = (0..self.recovery_count)
+            .filter(|index| self.received[self.recovery_base_pos + index])
+            .map(|index| (ShardKind::Recovery, index));
+
+        original.chain(recovery)
+    }
+
+    /// Returns number of original shards added
+    /// since this [`DecoderWork`] was reset or decoding was completed.
+    pub fn original_received_count(&self) -> usize {
+        self.original_received_count
+    }
+
+    /// Returns number of recovery shards added
+    /// since this [`DecoderWork`] was reset or decoding was completed.
+    pub fn recovery_received_count(&self) -> usize {
+        self.recovery_received_count
+    }
+
+    /// Restores this [`DecoderWork`] to the state it had
+    /// when given `snapshot` was taken.
+    ///
+    /// All received shards and the configuration are restored together,
+    /// so e.g. shards added after the snapshot are forgotten.
+    pub fn restore(&mut self, snapshot: DecoderWorkSnapshot) {
+        let DecoderWorkSnapshot {
+            original_count,
+            recovery_count,
+            shard_bytes,
+            original_base_pos,
+            recovery_base_pos,
+            original_received_count,
+            recovery_received_count,
+            received,
+            shards,
+        } = snapshot;
+
+        self.original_count = original_count;
+        self.recovery_count = recovery_count;
+        self.shard_bytes = shard_bytes;
+
+        self.original_base_pos = original_base_pos;
+        self.recovery_base_pos = recovery_base_pos;
+
+        self.original_received_count = original_received_count;
+        self.recovery_received_count = recovery_received_count;
+        self.received = received;
+        self.shards = shards;
+    }
+
+    /// Takes a snapshot of received shards and configuration
+    /// which can be later restored with [`DecoderWork::restore`].
+    ///
+    /// This copies the whole working space, e.g. to speculatively
+    /// decode with an optimistic set of shards and roll back
+    /// if the attempt fails.
+    pub fn snapshot(&self) -> DecoderWorkSnapshot {
+        DecoderWorkSnapshot {
+            original_count: self.original_count,
+            recovery_count: self.recovery_count,
+            shard_bytes: self.shard_bytes,
+
+            original_base_pos: self.original_base_pos,
+            recovery_base_pos: self.recovery_base_pos,
+
+            original_received_count: self.original_received_count,
+            recovery_received_count: self.recovery_received_count,
+            received: self.received.clone(),
+            shards: self.shards.clone(),
         }
     }
 }
 
+// ======================================================================
+// DecoderWorkSnapshot - PUBLIC
+
+/// Copy of the state of [`DecoderWork`].
+///
+/// This struct is created by [`DecoderWork::snapshot`]
+/// and consumed by [`DecoderWork::restore`].
+pub struct DecoderWorkSnapshot {
+    original_count: usize,
+    recovery_count: usize,
+    shard_bytes: usize,
+
+    original_base_pos: usize,
+    recovery_base_pos: usize,
+
+    original_received_count: usize,
+    recovery_received_count: usize,
+    received: FixedBitSet,
+    shards: Shards,
+}
+
+// ======================================================================
+// DecoderWork - IMPL Debug
+
+// Shard data is not shown so that user data doesn't end up in logs.
+impl fmt::Debug for DecoderWork {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DecoderWork")
+            .field("original_count", &self.original_count)
+            .field("recovery_count", &self.recovery_count)
+            .field("shard_bytes", &self.shard_bytes)
+            .field("original_received_count", &self.original_received_count)
+            .field("recovery_received_count", &self.recovery_received_count)
+            .field("shards", &self.shards)
+            .finish_non_exhaustive()
+    }
+}
+
 // ======================================================================
 // DecoderWork - IMPL Default
 
@@ -122,6 +274,11 @@ impl DecoderWork {
     pub(crate) fn decode_begin(
         &mut self,
     ) -> Result<Option<(ShardsRefMut<'_>, usize, usize, &FixedBitSet)>, Error> {
+        #[cfg(feature = "timing")]
+        {
+            self.decode_timings = None;
+        }
+
         if self.original_received_count + self.recovery_received_count < self.original_count {
             Err(Error::NotEnoughShards {
                 original_count: self.original_count,
@@ -144,6 +301,10 @@ impl DecoderWork {
         self.original_count
     }
 
+    pub(crate) fn recovery_count(&self) -> usize {
+        self.recovery_count
+    }
+
     pub(crate) fn reset(
         &mut self,
         original_count: usize,
@@ -166,6 +327,11 @@ impl DecoderWork {
         self.original_received_count = 0;
         self.recovery_received_count = 0;
 
+        #[cfg(feature = "timing")]
+        {
+            self.decode_timings = None;
+        }
+
         let max_received_pos = core::cmp::max(
             original_base_pos + original_count,
             recovery_base_pos + recovery_count,
@@ -176,7 +342,7 @@ impl DecoderWork {
             self.received.grow(max_received_pos);
         }
 
-        self.shards.resize(work_count, shard_bytes.div_ceil(64));
+        self.shards.resize(work_count, shard_bytes);
     }
 
     pub(crate) fn reset_received(&mut self) {
@@ -196,9 +362,31 @@ impl DecoderWork {
         }
     }
 
+    // This must only be called by `DecoderResult`.
+    //
+    // Copies restored original shard and then zeroes it and marks it
+    // as received, so that it no longer counts as restored.
+    pub(crate) fn take_restored_original(&mut self, index: usize) -> Option<Vec<u8>> {
+        let shard = self.restored_original(index)?.to_vec();
+        let pos = self.original_base_pos + index;
+
+        self.shards[pos].fill([0; 64]);
+        self.original_received_count += 1;
+        self.received.set(pos, true);
+        Some(shard)
+    }
+
+    #[cfg(feature = "timing")]
+    pub(crate) fn set_decode_timings(&mut self, decode_timings: DecodingTimings) {
+        self.decode_timings = Some(decode_timings);
+    }
+
+    pub(crate) fn shard_bytes(&self) -> usize {
+        self.shard_bytes
+    }
+
     pub(crate) fn undo_last_chunk_encoding(&mut self) {
         self.shards.undo_last_chunk_encoding(
-            self.shard_bytes,
             self.original_base_pos..self.original_base_pos + self.original_count,
         );
     }
@@ -207,3 +395,146 @@ impl DecoderWork {
         self.original_count - self.original_received_count
     }
 }
+
+// ======================================================================
+// TESTS
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        engine::NoSimd,
+        rate::{DefaultRate, HighRate, LowRate, Rate, RateDecoder},
+        test_util,
+    };
+
+    #[cfg(not(feature = "std"))]
+    use alloc::{format, vec::Vec};
+
+    fn new_work() -> DecoderWork {
+        let mut work = DecoderWork::new();
+        work.reset(3, 2, 64, 0, 4, 8);
+        work
+    }
+
+    #[test]
+    fn debug() {
+        let mut work = new_work();
+        work.add_original_shard(1, [123; 64]).unwrap();
+        work.add_recovery_shard(0, [123; 64]).unwrap();
+
+        let debug = format!("{work:?}");
+        for field in [
+            "original_count: 3",
+            "recovery_count: 2",
+            "shard_bytes: 64",
+            "original_received_count: 1",
+            "recovery_received_count: 1",
+            "shards: Shards {",
+        ] {
+            assert!(debug.contains(field), "{debug}");
+        }
+        assert!(!debug.contains("123"), "{debug}");
+    }
+
+    #[test]
+    fn iter_received() {
+        let original = test_util::generate_original(3, 64, 0);
+
+        let mut work = new_work();
+        assert_eq!(work.iter_received().count(), 0);
+
+        work.add_recovery_shard(1, &original[0]).unwrap();
+        work.add_original_shard(2, &original[2]).unwrap();
+        work.add_original_shard(0, &original[0]).unwrap();
+
+        assert_eq!(
+            work.iter_received().collect::<Vec<_>>(),
+            [
+                (ShardKind::Original, 0),
+                (ShardKind::Original, 2),
+                (ShardKind::Recovery, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn snapshot_restore_forgets_later_shards() {
+        let original = test_util::generate_original(3, 64, 0);
+
+        let mut work = new_work();
+        work.add_original_shard(0, &original[0]).unwrap();
+
+        let snapshot = work.snapshot();
+
+        work.add_original_shard(1, &original[1]).unwrap();
+        work.add_recovery_shard(0, &original[2]).unwrap();
+        assert_eq!(work.missing_original_count(), 1);
+
+        work.restore(snapshot);
+        assert_eq!(work.missing_original_count(), 2);
+
+        assert_eq!(
+            work.add_original_shard(0, &original[0]),
+            Err(Error::DuplicateOriginalShardIndex { index: 0 })
+        );
+        work.add_original_shard(1, &original[1]).unwrap();
+        work.add_recovery_shard(0, &original[2]).unwrap();
+    }
+
+    #[test]
+    fn snapshot_restore_after_decode() {
+        let original = test_util::generate_original(3, 64, 0);
+
+        let mut work = new_work();
+        work.add_original_shard(0, &original[0]).unwrap();
+        work.add_original_shard(2, &original[2]).unwrap();
+        work.add_recovery_shard(0, &original[1]).unwrap();
+
+        let snapshot = work.snapshot();
+
+        // Decoding modifies shards in-place.
+        let (mut shards, ..) = work.decode_begin().unwrap().unwrap();
+        shards.zero(..);
+        assert_eq!(work.shards[0].as_flattened(), &[0; 64]);
+
+        work.restore(snapshot);
+        assert_eq!(work.shards[0].as_flattened(), original[0]);
+        assert_eq!(work.shards[2].as_flattened(), original[2]);
+    }
+
+    #[test]
+    fn snapshot_restore_configuration() {
+        let mut work = new_work();
+        let snapshot = work.snapshot();
+
+        work.reset(10, 20, 128, 32, 0, 64);
+        work.restore(snapshot);
+
+        assert_eq!(work.original_count(), 3);
+        assert_eq!(work.recovery_count(), 2);
+        assert_eq!(work.shard_bytes(), 64);
+    }
+
+    fn test_work_count<R: Rate<NoSimd>>(work_count: fn(usize, usize) -> usize) {
+        for (original_count, recovery_count) in
+            [(1, 1), (3, 5), (100, 1000), (1000, 100), (4097, 1025)]
+        {
+            let decoder =
+                R::decoder(original_count, recovery_count, 100, NoSimd::new(), None).unwrap();
+            let (_, mut work) = decoder.into_parts();
+
+            assert_eq!(
+                work.shards.as_ref_mut().len(),
+                work_count(original_count, recovery_count)
+            );
+        }
+    }
+
+    #[test]
+    fn work_count() {
+        test_work_count::<HighRate<NoSimd>>(HighRate::<NoSimd>::decoder_work_count);
+        test_work_count::<LowRate<NoSimd>>(LowRate::<NoSimd>::decoder_work_count);
+        test_work_count::<DefaultRate<NoSimd>>(DefaultRate::<NoSimd>::decoder_work_count);
+    }
+}
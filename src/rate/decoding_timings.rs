@@ -0,0 +1,142 @@
+#[cfg(feature = "timing")]
+use std::time::Instant;
+
+use crate::rate::DecoderWork;
+
+// ======================================================================
+// DecodingTimings - PUBLIC
+
+/// Time spent in each step of the latest decoding, in microseconds.
+///
+/// This is returned by [`DecoderWork::decode_timings`]
+/// and [`DecoderResult::decode_timings`] when `timing` feature is enabled.
+/// See [algorithm] for what each step does.
+///
+/// [`DecoderResult::decode_timings`]: crate::DecoderResult::decode_timings
+/// [algorithm]: crate::algorithm
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DecodingTimings {
+    /// Finding erasure locations and evaluating the error locator polynomial.
+    pub eval_poly_us: u64,
+    /// Multiplying received shards with the evaluated polynomial.
+    pub mul_us: u64,
+    /// IFFT of the multiplied shards.
+    pub ifft_us: u64,
+    /// Formal derivative between IFFT and FFT.
+    pub formal_derivative_us: u64,
+    /// FFT of the derivative.
+    pub fft_us: u64,
+    /// Revealing erasures and undoing last chunk encoding of restored shards.
+    pub normalization_us: u64,
+}
+
+// ======================================================================
+// DecodingTimer - CRATE
+
+// Measures time spent in each step of decoding.
+// Without `timing` feature this is zero-sized and does nothing.
+pub(crate) struct DecodingTimer {
+    #[cfg(feature = "timing")]
+    last: Instant,
+    #[cfg(feature = "timing")]
+    timings: DecodingTimings,
+}
+
+impl DecodingTimer {
+    pub(crate) fn new() -> Self {
+        Self {
+            #[cfg(feature = "timing")]
+            last: Instant::now(),
+            #[cfg(feature = "timing")]
+            timings: DecodingTimings::default(),
+        }
+    }
+
+    // Stores timings into `work`, to be returned by `decode_timings`.
+    #[cfg_attr(not(feature = "timing"), allow(clippy::unused_self))]
+    pub(crate) fn finish(self, work: &mut DecoderWork) {
+        #[cfg(feature = "timing")]
+        work.set_decode_timings(self.timings);
+        #[cfg(not(feature = "timing"))]
+        let _ = work;
+    }
+
+    // Adds time since previous `lap` or `new` to field selected by `field`.
+    #[cfg_attr(not(feature = "timing"), allow(clippy::unused_self))]
+    #[inline]
+    pub(crate) fn lap(&mut self, field: fn(&mut DecodingTimings) -> &mut u64) {
+        #[cfg(feature = "timing")]
+        {
+            let now = Instant::now();
+            let elapsed = u64::try_from((now - self.last).as_micros()).unwrap_or(u64::MAX);
+            *field(&mut self.timings) += elapsed;
+            self.last = now;
+        }
+        #[cfg(not(feature = "timing"))]
+        let _ = field;
+    }
+}
+
+// ======================================================================
+// TESTS
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_util, ReedSolomonDecoder, ReedSolomonEncoder};
+
+    #[cfg(not(feature = "std"))]
+    use alloc::vec::Vec;
+
+    // Decodes with as many original shards missing as possible
+    // and returns the timings.
+    fn decode_timings(original_count: usize, recovery_count: usize) -> Option<DecodingTimings> {
+        let original = test_util::generate_original(original_count, 4096, 0);
+
+        let mut encoder = ReedSolomonEncoder::new(original_count, recovery_count, 4096).unwrap();
+        for shard in &original {
+            encoder.add_original_shard(shard).unwrap();
+        }
+        let result = encoder.encode().unwrap();
+        let recovery: Vec<_> = result.recovery_iter().collect();
+
+        let mut decoder = ReedSolomonDecoder::new(original_count, recovery_count, 4096).unwrap();
+        let missing_count = original_count.min(recovery_count);
+        for (index, shard) in original.iter().enumerate().skip(missing_count) {
+            decoder.add_original_shard(index, shard).unwrap();
+        }
+        for (index, shard) in recovery.iter().enumerate() {
+            decoder.add_recovery_shard(index, shard).unwrap();
+        }
+        let result = decoder.decode().unwrap();
+        assert_eq!(result.restored_original(0).unwrap(), original[0]);
+        result.decode_timings()
+    }
+
+    #[cfg(feature = "timing")]
+    #[test]
+    fn decode_timings_enabled() {
+        // high rate, low rate
+        for (original_count, recovery_count) in [(512, 1024), (1024, 512)] {
+            let timings = decode_timings(original_count, recovery_count).unwrap();
+
+            // Single steps may take less than 1 µs,
+            // so only the total is checked.
+            let total_us = timings.eval_poly_us
+                + timings.mul_us
+                + timings.ifft_us
+                + timings.formal_derivative_us
+                + timings.fft_us
+                + timings.normalization_us;
+            assert!(total_us > 0, "{timings:?}");
+        }
+    }
+
+    #[cfg(not(feature = "timing"))]
+    #[test]
+    fn decode_timings_disabled() {
+        assert_eq!(core::mem::size_of::<DecodingTimer>(), 0);
+        assert_eq!(decode_timings(512, 1024), None);
+        assert_eq!(decode_timings(1024, 512), None);
+    }
+}
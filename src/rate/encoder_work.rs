@@ -1,14 +1,40 @@
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use core::fmt;
+
 use crate::{
     engine::{Shards, ShardsRefMut},
     Error,
 };
 
+// ======================================================================
+// WorkState - PRIVATE
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum WorkState {
+    // No original shards received.
+    Idle,
+    // Some but not all original shards received.
+    ReceivedPartial,
+    // All original shards received, ready to encode.
+    ReceivedComplete,
+    // Encoding done, recovery shards available until `reset_received`.
+    EncodingDone,
+}
+
 // ======================================================================
 // EncoderWork - PUBLIC
 
 /// Working space for [`RateEncoder`].
 ///
+/// Working space has [`HighRate::encoder_work_count`] or
+/// [`LowRate::encoder_work_count`] shards
+/// for given configuration, each taking `shard_bytes` rounded up
+/// to a multiple of 64 bytes, e.g. for sizing memory budgets.
+///
 /// [`RateEncoder`]: crate::rate::RateEncoder
+/// [`HighRate::encoder_work_count`]: crate::rate::HighRate::encoder_work_count
+/// [`LowRate::encoder_work_count`]: crate::rate::LowRate::encoder_work_count
 pub struct EncoderWork {
     original_count: usize,
     recovery_count: usize,
@@ -17,6 +43,7 @@ pub struct EncoderWork {
 
     original_received_count: usize,
     shards: Shards,
+    state: WorkState,
 }
 
 impl EncoderWork {
@@ -30,8 +57,32 @@ impl EncoderWork {
 
             original_received_count: 0,
             shards: Shards::new(),
+            state: WorkState::Idle,
         }
     }
+
+    /// Returns number of original shards added
+    /// since this [`EncoderWork`] was reset or encoding was completed.
+    pub fn original_received_count(&self) -> usize {
+        self.original_received_count
+    }
+}
+
+// ======================================================================
+// EncoderWork - IMPL Debug
+
+// Shard data is not shown so that user data doesn't end up in logs.
+impl fmt::Debug for EncoderWork {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EncoderWork")
+            .field("original_count", &self.original_count)
+            .field("recovery_count", &self.recovery_count)
+            .field("shard_bytes", &self.shard_bytes)
+            .field("original_received_count", &self.original_received_count)
+            .field("state", &self.state)
+            .field("shards", &self.shards)
+            .finish()
+    }
 }
 
 // ======================================================================
@@ -53,7 +104,9 @@ impl EncoderWork {
     ) -> Result<(), Error> {
         let original_shard = original_shard.as_ref();
 
-        if self.original_received_count == self.original_count {
+        if self.state == WorkState::EncodingDone {
+            Err(Error::InvalidState)
+        } else if self.original_received_count == self.original_count {
             Err(Error::TooManyOriginalShards {
                 original_count: self.original_count,
             })
@@ -67,12 +120,20 @@ impl EncoderWork {
                 .insert(self.original_received_count, original_shard);
 
             self.original_received_count += 1;
+            self.state = if self.original_received_count == self.original_count {
+                WorkState::ReceivedComplete
+            } else {
+                WorkState::ReceivedPartial
+            };
             Ok(())
         }
     }
 
     pub(crate) fn encode_begin(&mut self) -> Result<(ShardsRefMut<'_>, usize, usize), Error> {
-        if self.original_received_count == self.original_count {
+        if self.state == WorkState::EncodingDone {
+            Err(Error::InvalidState)
+        } else if self.state == WorkState::ReceivedComplete {
+            self.state = WorkState::EncodingDone;
             Ok((
                 self.shards.as_ref_mut(),
                 self.original_count,
@@ -86,6 +147,24 @@ impl EncoderWork {
         }
     }
 
+    pub(crate) fn encoding_complete(&self) -> bool {
+        self.state == WorkState::EncodingDone
+    }
+
+    // Returns copy of original shard `index` if it has been added
+    // and hasn't been consumed by encoding yet.
+    //
+    // Shards are added in order, so these are `0..original_received_count`.
+    // Copy is returned as last chunk of a shard has different layout
+    // if `shard_bytes % 64 != 0`, see `Shards::insert`.
+    pub(crate) fn original_shard(&self, index: usize) -> Option<Vec<u8>> {
+        if self.state != WorkState::EncodingDone && index < self.original_received_count {
+            Some(self.shards.extract(index))
+        } else {
+            None
+        }
+    }
+
     // This must only be called by `EncoderResult`.
     pub(crate) fn recovery(&self, index: usize) -> Option<&[u8]> {
         if index < self.recovery_count {
@@ -95,6 +174,14 @@ impl EncoderWork {
         }
     }
 
+    // Copies recovery shard `index` into new `Vec` and zeroes it in working space.
+    // This must only be called by `EncoderResult`.
+    pub(crate) fn take_recovery_shard(&mut self, index: usize) -> Option<Vec<u8>> {
+        let shard = self.recovery(index)?.to_vec();
+        self.shards[index].fill([0; 64]);
+        Some(shard)
+    }
+
     pub(crate) fn reset(
         &mut self,
         original_count: usize,
@@ -109,19 +196,135 @@ impl EncoderWork {
         self.shard_bytes = shard_bytes;
 
         self.original_received_count = 0;
-        self.shards.resize(work_count, shard_bytes.div_ceil(64));
+        self.shards.resize(work_count, shard_bytes);
+        self.state = WorkState::Idle;
     }
 
+    // This must only be called by `EncoderResult`.
     pub(crate) fn reset_received(&mut self) {
+        debug_assert!(self.encoding_complete());
+
         self.original_received_count = 0;
+        self.state = WorkState::Idle;
     }
 
     pub(crate) fn undo_last_chunk_encoding(&mut self) {
-        self.shards
-            .undo_last_chunk_encoding(self.shard_bytes, 0..self.recovery_count);
+        self.shards.undo_last_chunk_encoding(0..self.recovery_count);
+    }
+
+    // Zeroes everything except recovery shards, i.e. original shards
+    // and intermediate results derived from them.
+    // This must only be called by `EncoderResult`.
+    pub(crate) fn zero_input(&mut self) {
+        debug_assert!(self.encoding_complete());
+
+        self.shards.as_ref_mut().zero(self.recovery_count..);
+    }
+
+    pub(crate) fn original_count(&self) -> usize {
+        self.original_count
     }
 
     pub(crate) fn recovery_count(&self) -> usize {
         self.recovery_count
     }
+
+    pub(crate) fn shard_bytes(&self) -> usize {
+        self.shard_bytes
+    }
+}
+
+// ======================================================================
+// TESTS
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        engine::NoSimd,
+        rate::{DefaultRate, HighRate, LowRate, Rate, RateEncoder},
+    };
+
+    #[cfg(not(feature = "std"))]
+    use alloc::format;
+
+    #[test]
+    fn debug() {
+        let mut work = EncoderWork::new();
+        work.reset(3, 2, 64, 4);
+        work.add_original_shard([123; 64]).unwrap();
+
+        assert_eq!(
+            format!("{work:?}"),
+            "EncoderWork { original_count: 3, recovery_count: 2, shard_bytes: 64, \
+             original_received_count: 1, state: ReceivedPartial, \
+             shards: Shards { shard_count: 4, shard_len_64: 1, data_len: 4, .. } }"
+        );
+    }
+
+    #[test]
+    fn zero_input() {
+        let mut work = EncoderWork::new();
+        work.reset(3, 2, 100, 4);
+        for i in 1..=3 {
+            work.add_original_shard([i; 100]).unwrap();
+        }
+        work.encode_begin().unwrap();
+        work.zero_input();
+
+        // Shards 0 and 1 are where recovery shards would be.
+        for index in 0..4 {
+            let (ptr, len) = unsafe { work.shards.shard_ptr_range(index) };
+            assert_eq!(len, 128);
+
+            let bytes = unsafe { core::slice::from_raw_parts(ptr, len) };
+            assert_eq!(bytes.iter().all(|&byte| byte == 0), index >= 2);
+        }
+    }
+
+    #[test]
+    fn original_shard() {
+        let mut work = EncoderWork::new();
+        work.reset(3, 2, 100, 4);
+        assert_eq!(work.original_shard(0), None);
+
+        let original: Vec<Vec<u8>> = (0..3u8)
+            .map(|i| (0..100).map(|j| i + j).collect())
+            .collect();
+
+        work.add_original_shard(&original[0]).unwrap();
+        work.add_original_shard(&original[1]).unwrap();
+        assert_eq!(work.original_shard(0).as_ref(), Some(&original[0]));
+        assert_eq!(work.original_shard(1).as_ref(), Some(&original[1]));
+        assert_eq!(work.original_shard(2), None);
+
+        work.add_original_shard(&original[2]).unwrap();
+        assert_eq!(work.original_shard(2).as_ref(), Some(&original[2]));
+
+        // Original shards are consumed by encoding.
+        work.encode_begin().unwrap();
+        assert_eq!(work.original_shard(0), None);
+    }
+
+    fn test_work_count<R: Rate<NoSimd>>(work_count: fn(usize, usize) -> usize) {
+        for (original_count, recovery_count) in
+            [(1, 1), (3, 5), (100, 1000), (1000, 100), (4097, 1025)]
+        {
+            let encoder =
+                R::encoder(original_count, recovery_count, 100, NoSimd::new(), None).unwrap();
+            let (_, mut work) = encoder.into_parts();
+
+            assert_eq!(
+                work.shards.as_ref_mut().len(),
+                work_count(original_count, recovery_count)
+            );
+        }
+    }
+
+    #[test]
+    fn work_count() {
+        test_work_count::<HighRate<NoSimd>>(HighRate::<NoSimd>::encoder_work_count);
+        test_work_count::<LowRate<NoSimd>>(LowRate::<NoSimd>::encoder_work_count);
+        test_work_count::<DefaultRate<NoSimd>>(DefaultRate::<NoSimd>::encoder_work_count);
+    }
 }
@@ -0,0 +1,68 @@
+use crate::engine;
+
+// ======================================================================
+// ProgressCallback - CRATE
+
+// Receives number of completed butterflies and total number of butterflies.
+pub(crate) trait ProgressCallback {
+    // `false` if callback ignores progress, in which case
+    // `Progress` doesn't count anything.
+    const ENABLED: bool;
+
+    fn report(&mut self, completed: usize, total: usize);
+}
+
+// ======================================================================
+// Callback - CRATE
+
+// User given callback.
+pub(crate) struct Callback<F: FnMut(usize, usize)>(pub(crate) F);
+
+impl<F: FnMut(usize, usize)> ProgressCallback for Callback<F> {
+    const ENABLED: bool = true;
+
+    fn report(&mut self, completed: usize, total: usize) {
+        (self.0)(completed, total);
+    }
+}
+
+// ======================================================================
+// NoProgress - CRATE
+
+// Used by plain `encode` / `decode` so that they do no progress bookkeeping.
+pub(crate) struct NoProgress;
+
+impl ProgressCallback for NoProgress {
+    const ENABLED: bool = false;
+
+    fn report(&mut self, _completed: usize, _total: usize) {}
+}
+
+// ======================================================================
+// Progress - CRATE
+
+// Reports progress of encoding/decoding as number of completed butterflies.
+pub(crate) struct Progress<C: ProgressCallback> {
+    completed: usize,
+    total: usize,
+    callback: C,
+}
+
+impl<C: ProgressCallback> Progress<C> {
+    // `total` is only called if `C::ENABLED`.
+    pub(crate) fn new(total: impl FnOnce() -> usize, callback: C) -> Self {
+        Self {
+            completed: 0,
+            total: if C::ENABLED { total() } else { 0 },
+            callback,
+        }
+    }
+
+    // Must be called after each FFT/IFFT with its `size` and `truncated_size`.
+    pub(crate) fn advance(&mut self, size: usize, truncated_size: usize) {
+        if C::ENABLED {
+            self.completed += engine::butterfly_count(size, truncated_size);
+            self.callback.report(self.completed, self.total);
+        }
+    }
+}
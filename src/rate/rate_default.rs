@@ -3,16 +3,43 @@ use core::{cmp::Ordering, marker::PhantomData};
 use crate::{
     engine::{Engine, GF_ORDER},
     rate::{
-        DecoderWork, EncoderWork, HighRateDecoder, HighRateEncoder, LowRateDecoder, LowRateEncoder,
-        Rate, RateDecoder, RateEncoder,
+        rate_high, rate_low, DecoderWork, EncoderWork, HighRate, HighRateDecoder, HighRateEncoder,
+        LowRate, LowRateDecoder, LowRateEncoder, Rate, RateDecoder, RateEncoder,
     },
     DecoderResult, EncoderResult, Error,
 };
 
 // ======================================================================
-// FUNCTIONS - PRIVATE
+// FUNCTIONS - CRATE
+
+// Number of butterflies in `DefaultRateEncoder::encode`
+// or `None` if given shard counts aren't supported.
+pub(crate) fn encode_butterfly_count(
+    original_count: usize,
+    recovery_count: usize,
+) -> Option<usize> {
+    match use_high_rate(original_count, recovery_count) {
+        Ok(true) => Some(rate_high::encode_butterfly_count(
+            original_count,
+            recovery_count,
+        )),
+        Ok(false) => Some(rate_low::encode_butterfly_count(
+            original_count,
+            recovery_count,
+        )),
+        Err(_) => None,
+    }
+}
+
+// Returns `true` if `DefaultRate` uses high rate with given shard counts.
+pub(crate) fn use_high_rate(original_count: usize, recovery_count: usize) -> Result<bool, Error> {
+    if original_count == 0 || recovery_count == 0 {
+        return Err(Error::InvalidShardCount {
+            original_count,
+            recovery_count,
+        });
+    }
 
-fn use_high_rate(original_count: usize, recovery_count: usize) -> Result<bool, Error> {
     if original_count > GF_ORDER || recovery_count > GF_ORDER {
         return Err(Error::UnsupportedShardCount {
             original_count,
@@ -26,7 +53,7 @@ fn use_high_rate(original_count: usize, recovery_count: usize) -> Result<bool, E
     let smaller_pow2 = core::cmp::min(original_count_pow2, recovery_count_pow2);
     let larger = core::cmp::max(original_count, recovery_count);
 
-    if original_count == 0 || recovery_count == 0 || smaller_pow2 + larger > GF_ORDER {
+    if smaller_pow2 + larger > GF_ORDER {
         return Err(Error::UnsupportedShardCount {
             original_count,
             recovery_count,
@@ -78,10 +105,60 @@ impl<E: Engine> Rate<E> for DefaultRate<E> {
     }
 }
 
+impl<E: Engine> DefaultRate<E> {
+    /// Returns number of shards in working space of [`DecoderWork`]
+    /// needed to decode with the rate selected for given shard counts,
+    /// or `0` if given shard counts aren't supported.
+    ///
+    /// Each of these shards takes `shard_bytes` rounded up
+    /// to a multiple of 64 bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simd_erasure_core::{engine::NoSimd, rate::DefaultRate};
+    ///
+    /// assert_eq!(DefaultRate::<NoSimd>::decoder_work_count(1000, 100), 2048);
+    /// ```
+    ///
+    /// [`DecoderWork`]: crate::rate::DecoderWork
+    pub fn decoder_work_count(original_count: usize, recovery_count: usize) -> usize {
+        match use_high_rate(original_count, recovery_count) {
+            Ok(true) => HighRate::<E>::decoder_work_count(original_count, recovery_count),
+            Ok(false) => LowRate::<E>::decoder_work_count(original_count, recovery_count),
+            Err(_) => 0,
+        }
+    }
+
+    /// Returns number of shards in working space of [`EncoderWork`]
+    /// needed to encode with the rate selected for given shard counts,
+    /// or `0` if given shard counts aren't supported.
+    ///
+    /// Each of these shards takes `shard_bytes` rounded up
+    /// to a multiple of 64 bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simd_erasure_core::{engine::NoSimd, rate::DefaultRate};
+    ///
+    /// assert_eq!(DefaultRate::<NoSimd>::encoder_work_count(1000, 100), 1024);
+    /// ```
+    ///
+    /// [`EncoderWork`]: crate::rate::EncoderWork
+    pub fn encoder_work_count(original_count: usize, recovery_count: usize) -> usize {
+        match use_high_rate(original_count, recovery_count) {
+            Ok(true) => HighRate::<E>::encoder_work_count(original_count, recovery_count),
+            Ok(false) => LowRate::<E>::encoder_work_count(original_count, recovery_count),
+            Err(_) => 0,
+        }
+    }
+}
+
 // ======================================================================
 // InnerEncoder - PRIVATE
 
-#[derive(Default)]
+#[derive(Debug, Default)]
 enum InnerEncoder<E: Engine> {
     High(HighRateEncoder<E>),
     Low(LowRateEncoder<E>),
@@ -101,6 +178,7 @@ enum InnerEncoder<E: Engine> {
 /// specifying [`Engine`] and [`EncoderWork`].
 ///
 /// [`ReedSolomonEncoder`]: crate::ReedSolomonEncoder
+#[derive(Debug)]
 pub struct DefaultRateEncoder<E: Engine>(InnerEncoder<E>);
 
 impl<E: Engine> RateEncoder<E> for DefaultRateEncoder<E> {
@@ -122,6 +200,17 @@ impl<E: Engine> RateEncoder<E> for DefaultRateEncoder<E> {
         }
     }
 
+    fn encode_with_progress<F: FnMut(usize, usize)>(
+        &mut self,
+        progress: F,
+    ) -> Result<EncoderResult<'_>, Error> {
+        match &mut self.0 {
+            InnerEncoder::High(high) => high.encode_with_progress(progress),
+            InnerEncoder::Low(low) => low.encode_with_progress(progress),
+            InnerEncoder::None => unreachable!(),
+        }
+    }
+
     fn into_parts(self) -> (E, EncoderWork) {
         match self.0 {
             InnerEncoder::High(high) => high.into_parts(),
@@ -206,10 +295,23 @@ impl<E: Engine> RateEncoder<E> for DefaultRateEncoder<E> {
     }
 }
 
+// ======================================================================
+// DefaultRateEncoder - CRATE
+
+impl<E: Engine> DefaultRateEncoder<E> {
+    pub(crate) fn work(&self) -> &EncoderWork {
+        match &self.0 {
+            InnerEncoder::High(high) => high.work(),
+            InnerEncoder::Low(low) => low.work(),
+            InnerEncoder::None => unreachable!(),
+        }
+    }
+}
+
 // ======================================================================
 // InnerDecoder - PRIVATE
 
-#[derive(Default)]
+#[derive(Debug, Default)]
 enum InnerDecoder<E: Engine> {
     High(HighRateDecoder<E>),
     Low(LowRateDecoder<E>),
@@ -229,6 +331,7 @@ enum InnerDecoder<E: Engine> {
 /// specifying [`Engine`] and [`DecoderWork`].
 ///
 /// [`ReedSolomonDecoder`]: crate::ReedSolomonDecoder
+#[derive(Debug)]
 pub struct DefaultRateDecoder<E: Engine>(InnerDecoder<E>);
 
 impl<E: Engine> RateDecoder<E> for DefaultRateDecoder<E> {
@@ -266,6 +369,17 @@ impl<E: Engine> RateDecoder<E> for DefaultRateDecoder<E> {
         }
     }
 
+    fn decode_with_progress<F: FnMut(usize, usize)>(
+        &mut self,
+        progress: F,
+    ) -> Result<DecoderResult<'_>, Error> {
+        match &mut self.0 {
+            InnerDecoder::High(high) => high.decode_with_progress(progress),
+            InnerDecoder::Low(low) => low.decode_with_progress(progress),
+            InnerDecoder::None => unreachable!(),
+        }
+    }
+
     fn into_parts(self) -> (E, DecoderWork) {
         match self.0 {
             InnerDecoder::High(high) => high.into_parts(),
@@ -350,13 +464,69 @@ impl<E: Engine> RateDecoder<E> for DefaultRateDecoder<E> {
     }
 }
 
+impl<E: Engine> DefaultRateDecoder<E> {
+    /// Consumes this decoder returning decoder which uses given `engine`
+    /// but keeps the configuration and all received shards.
+    ///
+    /// Adding shards doesn't use the [`Engine`], so this allows adding them
+    /// with one engine and decoding with another, e.g. a faster one.
+    /// Both engines must use the same [`GfField`], otherwise decoding
+    /// gives wrong results.
+    ///
+    /// [`GfField`]: crate::engine::GfField
+    pub fn with_engine<E2: Engine>(self, engine: E2) -> DefaultRateDecoder<E2> {
+        DefaultRateDecoder(match self.0 {
+            InnerDecoder::High(high) => InnerDecoder::High(high.with_engine(engine)),
+            InnerDecoder::Low(low) => InnerDecoder::Low(low.with_engine(engine)),
+            InnerDecoder::None => InnerDecoder::None,
+        })
+    }
+}
+
+// ======================================================================
+// DefaultRateDecoder - CRATE
+
+impl<E: Engine> DefaultRateDecoder<E> {
+    // Same as `decode` but uses given `engine` instead of own engine.
+    pub(crate) fn decode_with_engine<E2: Engine>(
+        &mut self,
+        engine: &E2,
+    ) -> Result<DecoderResult<'_>, Error> {
+        match &mut self.0 {
+            InnerDecoder::High(high) => high.decode_with_engine(engine),
+            InnerDecoder::Low(low) => low.decode_with_engine(engine),
+            InnerDecoder::None => unreachable!(),
+        }
+    }
+
+    pub(crate) fn work(&self) -> &DecoderWork {
+        match &self.0 {
+            InnerDecoder::High(high) => high.work(),
+            InnerDecoder::Low(low) => low.work(),
+            InnerDecoder::None => unreachable!(),
+        }
+    }
+}
+
 // ======================================================================
 // TESTS
 
 #[cfg(test)]
 mod tests {
+    #[cfg(not(feature = "std"))]
+    use alloc::vec::Vec;
+
+    use core::cell::Cell;
+
     use super::*;
-    use crate::test_util;
+    use crate::{
+        engine::{
+            tables, DefaultEngine, GfElement, GfField, GfPolynomial, NoSimd, ShardsRefMut,
+            GF_MODULUS,
+        },
+        rate::{HighRate, LowRate},
+        test_util,
+    };
 
     // ============================================================
     // ROUNDTRIPS - SINGLE ROUND
@@ -430,6 +600,316 @@ mod tests {
         );
     }
 
+    // ============================================================
+    // ROUNDTRIPS - CUSTOM FIELD
+
+    fn roundtrip_with_engine(
+        engine: NoSimd,
+        original_count: usize,
+        recovery_count: usize,
+        recovery_hash: &str,
+        decoder_original: &[usize],
+        decoder_recovery: &[usize],
+        seed: u8,
+    ) {
+        let mut encoder =
+            DefaultRateEncoder::new(original_count, recovery_count, 1024, engine, None).unwrap();
+        let mut decoder =
+            DefaultRateDecoder::new(original_count, recovery_count, 1024, engine, None).unwrap();
+
+        test_util::roundtrip::<DefaultRate<_>, _, _>(
+            &mut encoder,
+            &mut decoder,
+            original_count,
+            1024,
+            recovery_hash,
+            decoder_original,
+            decoder_recovery,
+            seed,
+        );
+    }
+
+    #[test]
+    fn roundtrip_custom_field() {
+        let field = GfField::new(GfPolynomial(0x100B), 2).unwrap();
+        let engine = NoSimd::new_with_field(field);
+
+        roundtrip_with_engine(
+            engine,
+            3,
+            2,
+            test_util::HIGH_3_2_FIELD_1100B,
+            &[1],
+            &[0, 1],
+            132,
+        );
+        roundtrip_with_engine(
+            engine,
+            2,
+            3,
+            test_util::LOW_2_3_FIELD_1100B,
+            &[],
+            &[0, 2],
+            123,
+        );
+    }
+
+    #[test]
+    fn roundtrip_default_field() {
+        let engine = NoSimd::new_with_field(GfField::DEFAULT);
+
+        roundtrip_with_engine(engine, 3, 2, test_util::HIGH_3_2, &[1], &[0, 1], 132);
+        roundtrip_with_engine(engine, 2, 3, test_util::LOW_2_3, &[], &[0, 2], 123);
+    }
+
+    // ============================================================
+    // with_engine
+
+    #[test]
+    fn with_engine() {
+        for (original_count, recovery_count, seed) in [(3, 2, 132), (2, 3, 123)] {
+            let original = test_util::generate_original(original_count, 1024, seed);
+
+            let mut encoder =
+                DefaultRateEncoder::new(original_count, recovery_count, 1024, NoSimd::new(), None)
+                    .unwrap();
+            for shard in &original {
+                encoder.add_original_shard(shard).unwrap();
+            }
+            let recovery: Vec<Vec<u8>> = encoder
+                .encode()
+                .unwrap()
+                .recovery_iter()
+                .map(<[u8]>::to_vec)
+                .collect();
+
+            let mut decoder =
+                DefaultRateDecoder::new(original_count, recovery_count, 1024, NoSimd::new(), None)
+                    .unwrap();
+            for (index, shard) in original.iter().enumerate().skip(1) {
+                decoder.add_original_shard(index, shard).unwrap();
+            }
+            decoder.add_recovery_shard(1, &recovery[1]).unwrap();
+
+            let mut decoder = decoder.with_engine(DefaultEngine::new());
+            assert_eq!(decoder.work().original_received_count(), original_count - 1);
+
+            let result = decoder.decode().unwrap();
+            assert_eq!(result.restored_original(0), Some(&original[0][..]));
+        }
+    }
+
+    // ============================================================
+    // decoder_work_count / encoder_work_count
+
+    #[test]
+    fn work_count() {
+        for (original_count, recovery_count) in [(3, 3), (3, 5), (5, 3), (1000, 100), (100, 1000)] {
+            let (decoder_expected, encoder_expected) =
+                if super::use_high_rate(original_count, recovery_count).unwrap() {
+                    (
+                        HighRate::<NoSimd>::decoder_work_count(original_count, recovery_count),
+                        HighRate::<NoSimd>::encoder_work_count(original_count, recovery_count),
+                    )
+                } else {
+                    (
+                        LowRate::<NoSimd>::decoder_work_count(original_count, recovery_count),
+                        LowRate::<NoSimd>::encoder_work_count(original_count, recovery_count),
+                    )
+                };
+
+            assert_eq!(
+                DefaultRate::<NoSimd>::decoder_work_count(original_count, recovery_count),
+                decoder_expected
+            );
+            assert_eq!(
+                DefaultRate::<NoSimd>::encoder_work_count(original_count, recovery_count),
+                encoder_expected
+            );
+            assert_ne!(encoder_expected, 0);
+        }
+
+        assert_eq!(DefaultRate::<NoSimd>::decoder_work_count(0, 1), 0);
+        assert_eq!(DefaultRate::<NoSimd>::encoder_work_count(0, 1), 0);
+    }
+
+    // ============================================================
+    // butterfly_count
+
+    // Engine doing FFT/IFFT butterfly by butterfly using `NoSimd`,
+    // counting the butterflies actually done.
+    #[derive(Debug)]
+    struct CountingEngine {
+        inner: NoSimd,
+        butterflies: Cell<usize>,
+    }
+
+    impl CountingEngine {
+        fn butterfly(&self, a: &mut [[u8; 64]], b: &mut [[u8; 64]], log_m: GfElement, fft: bool) {
+            if fft {
+                if log_m != GF_MODULUS {
+                    self.inner.xor_then_mul(a, b, log_m);
+                }
+                self.inner.add_xor(b, a);
+            } else {
+                self.inner.add_xor(b, a);
+                if log_m != GF_MODULUS {
+                    self.inner.xor_then_mul(a, b, log_m);
+                }
+            }
+            self.butterflies.set(self.butterflies.get() + 1);
+        }
+
+        fn layer(
+            &self,
+            data: &mut ShardsRefMut,
+            pos: usize,
+            dist: usize,
+            truncated_size: usize,
+            skew_delta: usize,
+            fft: bool,
+        ) {
+            let skew = tables::get_skew();
+            let mut r = 0;
+            while r < truncated_size {
+                let log_m = skew[r + dist + skew_delta - 1];
+                for i in r..r + dist {
+                    let (a, b) = data.dist2_mut(pos + i, dist);
+                    self.butterfly(a, b, log_m, fft);
+                }
+                r += dist * 2;
+            }
+        }
+    }
+
+    impl Engine for CountingEngine {
+        fn fft(
+            &self,
+            data: &mut ShardsRefMut,
+            pos: usize,
+            size: usize,
+            truncated_size: usize,
+            skew_delta: usize,
+        ) {
+            let mut dist = size / 2;
+            while dist > 0 {
+                self.layer(data, pos, dist, truncated_size, skew_delta, true);
+                dist /= 2;
+            }
+        }
+
+        fn ifft(
+            &self,
+            data: &mut ShardsRefMut,
+            pos: usize,
+            size: usize,
+            truncated_size: usize,
+            skew_delta: usize,
+        ) {
+            let mut dist = 1;
+            while dist < size {
+                self.layer(data, pos, dist, truncated_size, skew_delta, false);
+                dist *= 2;
+            }
+        }
+
+        fn mul(&self, x: &mut [[u8; 64]], log_m: GfElement) {
+            self.inner.mul(x, log_m);
+        }
+    }
+
+    #[test]
+    fn butterfly_count_matches_counted_butterflies() {
+        for (original_count, recovery_count) in [
+            (1, 1),
+            (2, 3),
+            (3, 2),
+            (100, 1000),
+            (1000, 100),
+            (1024, 1024),
+            (1025, 1025),
+            (3000, 300),
+        ] {
+            let original = test_util::generate_original(original_count, 64, 0);
+
+            let engine = CountingEngine {
+                inner: NoSimd::new(),
+                butterflies: Cell::new(0),
+            };
+            let mut encoder =
+                DefaultRateEncoder::new(original_count, recovery_count, 64, engine, None).unwrap();
+            let mut expected_encoder =
+                DefaultRateEncoder::new(original_count, recovery_count, 64, NoSimd::new(), None)
+                    .unwrap();
+
+            for original in &original {
+                encoder.add_original_shard(original).unwrap();
+                expected_encoder.add_original_shard(original).unwrap();
+            }
+
+            // Counting engine must really do the encoding.
+            assert!(encoder
+                .encode()
+                .unwrap()
+                .recovery_iter()
+                .eq(expected_encoder.encode().unwrap().recovery_iter()));
+
+            let (engine, _) = encoder.into_parts();
+            let counted = engine.butterflies.get() as u64;
+            let estimate = crate::rate::butterfly_count(original_count, recovery_count);
+
+            assert!(
+                counted.abs_diff(estimate) * 10 <= counted,
+                "{original_count}/{recovery_count}: estimate {estimate}, counted {counted}"
+            );
+        }
+    }
+
+    // ============================================================
+    // estimated_work_units
+
+    #[test]
+    fn estimated_work_units() {
+        // Borderline cases around switchover between high and low rate.
+        for (original_count, recovery_count) in [
+            (3, 3),
+            (3, 4),
+            (3, 5),
+            (4, 3),
+            (5, 3),
+            (4, 4),
+            (1000, 1024),
+            (1024, 1000),
+            (1024, 1025),
+            (1025, 1024),
+            (61440, 4096),
+            (4096, 61440),
+        ] {
+            let high = HighRate::<NoSimd>::estimated_work_units(original_count, recovery_count);
+            let low = LowRate::<NoSimd>::estimated_work_units(original_count, recovery_count);
+            let default = crate::rate::butterfly_count(original_count, recovery_count);
+
+            let expected = if super::use_high_rate(original_count, recovery_count).unwrap() {
+                high
+            } else {
+                low
+            };
+            assert_eq!(default, expected);
+            assert_ne!(default, 0);
+
+            // Different power-of-two sizes, selected rate needs less work.
+            if original_count.next_power_of_two() != recovery_count.next_power_of_two()
+                && high != 0
+                && low != 0
+            {
+                assert_eq!(default, high.min(low));
+            }
+        }
+
+        assert_eq!(HighRate::<NoSimd>::estimated_work_units(4096, 61440), 0);
+        assert_eq!(LowRate::<NoSimd>::estimated_work_units(61440, 4096), 0);
+    }
+
     // ============================================================
     // use_high_rate
 
@@ -442,9 +922,17 @@ mod tests {
             })
         }
 
+        fn invalid(original_count: usize, recovery_count: usize) -> Result<bool, Error> {
+            Err(Error::InvalidShardCount {
+                original_count,
+                recovery_count,
+            })
+        }
+
         for (original_count, recovery_count, expected) in [
-            (0, 1, err(0, 1)),
-            (1, 0, err(1, 0)),
+            (0, 1, invalid(0, 1)),
+            (1, 0, invalid(1, 0)),
+            (0, 0, invalid(0, 0)),
             // CORRECT/WRONG RATE
             (3, 3, Ok(true)),
             (3, 4, Ok(true)),
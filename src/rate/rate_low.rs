@@ -1,11 +1,32 @@
 use core::marker::PhantomData;
 
 use crate::{
-    engine::{self, Engine, GF_MODULUS, GF_ORDER},
-    rate::{DecoderWork, EncoderWork, Rate, RateDecoder, RateEncoder},
+    engine::{self, Engine, ShardsRefMut, GF_MODULUS, GF_ORDER},
+    rate::{
+        decoding_timings::DecodingTimer,
+        progress::{Callback, NoProgress, Progress, ProgressCallback},
+        DecoderWork, EncoderWork, Rate, RateDecoder, RateEncoder,
+    },
     DecoderResult, EncoderResult, Error,
 };
 
+// ======================================================================
+// FUNCTIONS - CRATE
+
+// Number of butterflies in `LowRateEncoder::encode`.
+pub(crate) fn encode_butterfly_count(original_count: usize, recovery_count: usize) -> usize {
+    let chunk_size = original_count.next_power_of_two();
+
+    let mut count = engine::butterfly_count(chunk_size, original_count);
+    let mut chunk_start = 0;
+    while chunk_start < recovery_count {
+        let chunk_count = core::cmp::min(recovery_count - chunk_start, chunk_size);
+        count += engine::butterfly_count(chunk_size, chunk_count);
+        chunk_start += chunk_size;
+    }
+    count
+}
+
 // ======================================================================
 // LowRate - PUBLIC
 
@@ -25,10 +46,97 @@ impl<E: Engine> Rate<E> for LowRate<E> {
     }
 }
 
+impl<E: Engine> LowRate<E> {
+    /// Returns number of shards in working space of [`DecoderWork`]
+    /// needed to decode with low rate,
+    /// or `0` if given shard counts aren't supported.
+    ///
+    /// Each of these shards takes `shard_bytes` rounded up
+    /// to a multiple of 64 bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simd_erasure_core::{engine::NoSimd, rate::LowRate};
+    ///
+    /// assert_eq!(LowRate::<NoSimd>::decoder_work_count(1000, 100), 2048);
+    /// ```
+    ///
+    /// [`DecoderWork`]: crate::rate::DecoderWork
+    pub fn decoder_work_count(original_count: usize, recovery_count: usize) -> usize {
+        if Self::supports(original_count, recovery_count) {
+            LowRateDecoder::<E>::work_count(original_count, recovery_count)
+        } else {
+            0
+        }
+    }
+
+    /// Returns number of shards in working space of [`EncoderWork`]
+    /// needed to encode with low rate,
+    /// or `0` if given shard counts aren't supported.
+    ///
+    /// Each of these shards takes `shard_bytes` rounded up
+    /// to a multiple of 64 bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simd_erasure_core::{engine::NoSimd, rate::LowRate};
+    ///
+    /// assert_eq!(LowRate::<NoSimd>::encoder_work_count(1000, 100), 1024);
+    /// ```
+    ///
+    /// [`EncoderWork`]: crate::rate::EncoderWork
+    pub fn encoder_work_count(original_count: usize, recovery_count: usize) -> usize {
+        if Self::supports(original_count, recovery_count) {
+            LowRateEncoder::<E>::work_count(original_count, recovery_count)
+        } else {
+            0
+        }
+    }
+
+    /// Returns estimated amount of work needed to encode with low rate,
+    /// as number of FFT/IFFT butterfly operations,
+    /// or `0` if given shard counts aren't supported.
+    ///
+    /// Comparing this between [`HighRate`] and [`LowRate`] shows
+    /// which rate needs less work for given shard counts.
+    /// Note that [`DefaultRate`] selects rate based on
+    /// both encoding and decoding, so it doesn't always select
+    /// the rate with smallest estimate for encoding.
+    /// Work of [`DefaultRate`] is given by [`rate::butterfly_count`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simd_erasure_core::{
+    ///     engine::NoSimd,
+    ///     rate::{self, HighRate, LowRate},
+    /// };
+    ///
+    /// assert_eq!(HighRate::<NoSimd>::estimated_work_units(1025, 1024), 11263);
+    /// assert_eq!(LowRate::<NoSimd>::estimated_work_units(1025, 1024), 13311);
+    /// assert_eq!(rate::butterfly_count(1025, 1024), 11263);
+    /// ```
+    ///
+    /// [`DefaultRate`]: crate::rate::DefaultRate
+    /// [`rate::butterfly_count`]: crate::rate::butterfly_count
+    /// [`HighRate`]: crate::rate::HighRate
+    /// [`LowRate`]: crate::rate::LowRate
+    pub fn estimated_work_units(original_count: usize, recovery_count: usize) -> u64 {
+        if Self::supports(original_count, recovery_count) {
+            encode_butterfly_count(original_count, recovery_count) as u64
+        } else {
+            0
+        }
+    }
+}
+
 // ======================================================================
 // LowRateEncoder - PUBLIC
 
 /// Reed-Solomon encoder using only low rate.
+#[derive(Debug)]
 pub struct LowRateEncoder<E: Engine> {
     engine: E,
     work: EncoderWork,
@@ -42,17 +150,85 @@ impl<E: Engine> RateEncoder<E> for LowRateEncoder<E> {
     }
 
     fn encode(&mut self) -> Result<EncoderResult<'_>, Error> {
+        self.encode_inner(NoProgress)
+    }
+
+    fn encode_with_progress<F: FnMut(usize, usize)>(
+        &mut self,
+        progress: F,
+    ) -> Result<EncoderResult<'_>, Error> {
+        self.encode_inner(Callback(progress))
+    }
+
+    fn into_parts(self) -> (E, EncoderWork) {
+        (self.engine, self.work)
+    }
+
+    fn new(
+        original_count: usize,
+        recovery_count: usize,
+        shard_bytes: usize,
+        engine: E,
+        work: Option<EncoderWork>,
+    ) -> Result<Self, Error> {
+        let mut work = work.unwrap_or_default();
+        Self::reset_work(original_count, recovery_count, shard_bytes, &mut work)?;
+        Ok(Self { engine, work })
+    }
+
+    fn reset(
+        &mut self,
+        original_count: usize,
+        recovery_count: usize,
+        shard_bytes: usize,
+    ) -> Result<(), Error> {
+        Self::reset_work(original_count, recovery_count, shard_bytes, &mut self.work)
+    }
+}
+
+// ======================================================================
+// LowRateEncoder - CRATE
+
+impl<E: Engine> LowRateEncoder<E> {
+    pub(crate) fn work(&self) -> &EncoderWork {
+        &self.work
+    }
+}
+
+// ======================================================================
+// LowRateEncoder - PRIVATE
+
+impl<E: Engine> LowRateEncoder<E> {
+    // Shared implementation of `encode` and `encode_with_progress`.
+    fn encode_inner<C: ProgressCallback>(
+        &mut self,
+        callback: C,
+    ) -> Result<EncoderResult<'_>, Error> {
         let (mut work, original_count, recovery_count) = self.work.encode_begin()?;
         let chunk_size = original_count.next_power_of_two();
         let engine = &self.engine;
 
+        let mut progress = Progress::new(
+            || encode_butterfly_count(original_count, recovery_count),
+            callback,
+        );
+
         // ZEROPAD ORIGINAL
 
         work.zero(original_count..chunk_size);
 
         // IFFT - ORIGINAL
 
-        engine.ifft(&mut work, 0, chunk_size, original_count, 0);
+        // IFFT and FFT of all-zero data are all-zero, so if all
+        // original shards are all-zero then so are recovery shards
+        // and only copying the zeroes to other chunks is needed.
+
+        let is_zero = Self::is_zero_chunk(engine, &work, 0, original_count);
+
+        if !is_zero {
+            engine.ifft(&mut work, 0, chunk_size, original_count, 0);
+        }
+        progress.advance(chunk_size, original_count);
 
         // COPY IFFT RESULT TO OTHER CHUNKS
 
@@ -66,7 +242,10 @@ impl<E: Engine> RateEncoder<E> for LowRateEncoder<E> {
 
         let mut chunk_start = 0;
         while chunk_start + chunk_size <= recovery_count {
-            engine::fft_skew_end(engine, &mut work, chunk_start, chunk_size, chunk_size);
+            if !is_zero {
+                engine::fft_skew_end(engine, &mut work, chunk_start, chunk_size, chunk_size);
+            }
+            progress.advance(chunk_size, chunk_size);
             chunk_start += chunk_size;
         }
 
@@ -74,7 +253,10 @@ impl<E: Engine> RateEncoder<E> for LowRateEncoder<E> {
 
         let last_count = recovery_count % chunk_size;
         if last_count > 0 {
-            engine::fft_skew_end(engine, &mut work, chunk_start, chunk_size, last_count);
+            if !is_zero {
+                engine::fft_skew_end(engine, &mut work, chunk_start, chunk_size, last_count);
+            }
+            progress.advance(chunk_size, last_count);
         }
 
         // UNDO LAST CHUNK ENCODING
@@ -86,36 +268,11 @@ impl<E: Engine> RateEncoder<E> for LowRateEncoder<E> {
         Ok(EncoderResult::new(&mut self.work))
     }
 
-    fn into_parts(self) -> (E, EncoderWork) {
-        (self.engine, self.work)
-    }
-
-    fn new(
-        original_count: usize,
-        recovery_count: usize,
-        shard_bytes: usize,
-        engine: E,
-        work: Option<EncoderWork>,
-    ) -> Result<Self, Error> {
-        let mut work = work.unwrap_or_default();
-        Self::reset_work(original_count, recovery_count, shard_bytes, &mut work)?;
-        Ok(Self { engine, work })
-    }
-
-    fn reset(
-        &mut self,
-        original_count: usize,
-        recovery_count: usize,
-        shard_bytes: usize,
-    ) -> Result<(), Error> {
-        Self::reset_work(original_count, recovery_count, shard_bytes, &mut self.work)
+    // Returns `true` if shards `start .. start + count` are all-zero.
+    fn is_zero_chunk(engine: &E, work: &ShardsRefMut, start: usize, count: usize) -> bool {
+        (start..start + count).all(|i| engine.is_zero_shard(&work[i]))
     }
-}
-
-// ======================================================================
-// LowRateEncoder - PRIVATE
 
-impl<E: Engine> LowRateEncoder<E> {
     fn reset_work(
         original_count: usize,
         recovery_count: usize,
@@ -145,6 +302,7 @@ impl<E: Engine> LowRateEncoder<E> {
 // LowRateDecoder - PUBLIC
 
 /// Reed-Solomon decoder using only low rate.
+#[derive(Debug)]
 pub struct LowRateDecoder<E: Engine> {
     engine: E,
     work: DecoderWork,
@@ -170,17 +328,101 @@ impl<E: Engine> RateDecoder<E> for LowRateDecoder<E> {
     }
 
     fn decode(&mut self) -> Result<DecoderResult<'_>, Error> {
+        Self::decode_inner(&self.engine, &mut self.work, NoProgress)
+    }
+
+    fn decode_with_progress<F: FnMut(usize, usize)>(
+        &mut self,
+        progress: F,
+    ) -> Result<DecoderResult<'_>, Error> {
+        Self::decode_inner(&self.engine, &mut self.work, Callback(progress))
+    }
+
+    fn into_parts(self) -> (E, DecoderWork) {
+        (self.engine, self.work)
+    }
+
+    fn new(
+        original_count: usize,
+        recovery_count: usize,
+        shard_bytes: usize,
+        engine: E,
+        work: Option<DecoderWork>,
+    ) -> Result<Self, Error> {
+        let mut work = work.unwrap_or_default();
+        Self::reset_work(original_count, recovery_count, shard_bytes, &mut work)?;
+        Ok(Self { engine, work })
+    }
+
+    fn reset(
+        &mut self,
+        original_count: usize,
+        recovery_count: usize,
+        shard_bytes: usize,
+    ) -> Result<(), Error> {
+        Self::reset_work(original_count, recovery_count, shard_bytes, &mut self.work)
+    }
+}
+
+impl<E: Engine> LowRateDecoder<E> {
+    /// Consumes this decoder returning decoder which uses given `engine`
+    /// but keeps the configuration and all received shards.
+    ///
+    /// Adding shards doesn't use the [`Engine`], so this allows adding them
+    /// with one engine and decoding with another, e.g. a faster one.
+    /// Both engines must use the same [`GfField`], otherwise decoding
+    /// gives wrong results.
+    ///
+    /// [`GfField`]: crate::engine::GfField
+    pub fn with_engine<E2: Engine>(self, engine: E2) -> LowRateDecoder<E2> {
+        LowRateDecoder {
+            engine,
+            work: self.work,
+        }
+    }
+}
+
+// ======================================================================
+// LowRateDecoder - CRATE
+
+impl<E: Engine> LowRateDecoder<E> {
+    // Same as `decode` but uses given `engine` instead of own engine.
+    pub(crate) fn decode_with_engine<E2: Engine>(
+        &mut self,
+        engine: &E2,
+    ) -> Result<DecoderResult<'_>, Error> {
+        Self::decode_inner(engine, &mut self.work, NoProgress)
+    }
+
+    pub(crate) fn work(&self) -> &DecoderWork {
+        &self.work
+    }
+}
+
+// ======================================================================
+// LowRateDecoder - PRIVATE
+
+impl<E: Engine> LowRateDecoder<E> {
+    // Shared implementation of `decode`, `decode_with_progress`
+    // and `decode_with_engine`.
+    fn decode_inner<'a, E2: Engine, C: ProgressCallback>(
+        engine: &E2,
+        decoder_work: &'a mut DecoderWork,
+        callback: C,
+    ) -> Result<DecoderResult<'a>, Error> {
         let Some((mut work, original_count, recovery_count, received)) =
-            self.work.decode_begin()?
+            decoder_work.decode_begin()?
         else {
             // Nothing to do, original data is complete.
-            return Ok(DecoderResult::new(&mut self.work));
+            return Ok(DecoderResult::new(decoder_work));
         };
 
         let chunk_size = original_count.next_power_of_two();
         let recovery_end = chunk_size + recovery_count;
         let work_count = work.len();
 
+        let mut timer = DecodingTimer::new();
+
         // ERASURE LOCATIONS
 
         let mut erasures = [0; GF_ORDER];
@@ -201,7 +443,9 @@ impl<E: Engine> RateDecoder<E> for LowRateDecoder<E> {
 
         // EVALUATE POLYNOMIAL
 
-        E::eval_poly(&mut erasures, GF_ORDER);
+        engine.eval_poly_in_field(&mut erasures, GF_ORDER);
+
+        timer.lap(|t| &mut t.eval_poly_us);
 
         // MULTIPLY SHARDS
 
@@ -211,78 +455,63 @@ impl<E: Engine> RateDecoder<E> for LowRateDecoder<E> {
         // work[recovery_end   ..               ] = 0
 
         for i in 0..original_count {
-            if received[i] {
-                self.engine.mul(&mut work[i], erasures[i]);
-            } else {
+            if !received[i] {
                 work[i].fill([0; 64]);
+            } else if !engine.is_zero_shard(&work[i]) {
+                engine.mul(&mut work[i], erasures[i]);
             }
         }
 
         work.zero(original_count..chunk_size);
 
         for i in chunk_size..recovery_end {
-            if received[i] {
-                self.engine.mul(&mut work[i], erasures[i]);
-            } else {
+            if !received[i] {
                 work[i].fill([0; 64]);
+            } else if !engine.is_zero_shard(&work[i]) {
+                engine.mul(&mut work[i], erasures[i]);
             }
         }
 
         work.zero(recovery_end..);
 
+        timer.lap(|t| &mut t.mul_us);
+
         // IFFT / FORMAL DERIVATIVE / FFT
 
-        self.engine.ifft(&mut work, 0, work_count, recovery_end, 0);
+        let mut progress = Progress::new(
+            || 2 * engine::butterfly_count(work_count, recovery_end),
+            callback,
+        );
+
+        engine.ifft(&mut work, 0, work_count, recovery_end, 0);
+        progress.advance(work_count, recovery_end);
+        timer.lap(|t| &mut t.ifft_us);
         engine::formal_derivative(&mut work);
-        self.engine.fft(&mut work, 0, work_count, recovery_end, 0);
+        timer.lap(|t| &mut t.formal_derivative_us);
+        engine.fft(&mut work, 0, work_count, recovery_end, 0);
+        progress.advance(work_count, recovery_end);
+        timer.lap(|t| &mut t.fft_us);
 
         // REVEAL ERASURES
 
         for i in 0..original_count {
             if !received[i] {
-                self.engine.mul(&mut work[i], GF_MODULUS - erasures[i]);
+                engine.mul(&mut work[i], GF_MODULUS - erasures[i]);
             }
         }
 
         // UNDO LAST CHUNK ENCODING
 
-        self.work.undo_last_chunk_encoding();
+        decoder_work.undo_last_chunk_encoding();
 
-        // DONE
+        timer.lap(|t| &mut t.normalization_us);
+        timer.finish(decoder_work);
 
-        Ok(DecoderResult::new(&mut self.work))
-    }
-
-    fn into_parts(self) -> (E, DecoderWork) {
-        (self.engine, self.work)
-    }
-
-    fn new(
-        original_count: usize,
-        recovery_count: usize,
-        shard_bytes: usize,
-        engine: E,
-        work: Option<DecoderWork>,
-    ) -> Result<Self, Error> {
-        let mut work = work.unwrap_or_default();
-        Self::reset_work(original_count, recovery_count, shard_bytes, &mut work)?;
-        Ok(Self { engine, work })
-    }
+        // DONE
 
-    fn reset(
-        &mut self,
-        original_count: usize,
-        recovery_count: usize,
-        shard_bytes: usize,
-    ) -> Result<(), Error> {
-        Self::reset_work(original_count, recovery_count, shard_bytes, &mut self.work)
+        Ok(DecoderResult::new(decoder_work))
     }
-}
 
-// ======================================================================
-// LowRateDecoder - PRIVATE
-
-impl<E: Engine> LowRateDecoder<E> {
     fn reset_work(
         original_count: usize,
         recovery_count: usize,
@@ -318,7 +547,7 @@ impl<E: Engine> LowRateDecoder<E> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::test_util;
+    use crate::{engine::NoSimd, test_util};
 
     // ============================================================
     // ROUNDTRIPS - SINGLE ROUND
@@ -417,6 +646,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn roundtrip_zero_original() {
+        let original = vec![vec![0u8; 64]; 3];
+
+        let mut encoder = LowRate::<NoSimd>::encoder(3, 5, 64, NoSimd::new(), None).unwrap();
+        for shard in &original {
+            encoder.add_original_shard(shard).unwrap();
+        }
+        let result = encoder.encode().unwrap();
+        let recovery: Vec<_> = result.recovery_iter().map(<[u8]>::to_vec).collect();
+        assert!(recovery.iter().all(|shard| shard == &[0; 64]));
+        drop(result);
+
+        let mut decoder = LowRate::<NoSimd>::decoder(3, 5, 64, NoSimd::new(), None).unwrap();
+        for (i, shard) in recovery.iter().enumerate().skip(2) {
+            decoder.add_recovery_shard(i, shard).unwrap();
+        }
+        let result = decoder.decode().unwrap();
+        for i in 0..3 {
+            assert_eq!(result.restored_original(i).unwrap(), original[i]);
+        }
+        drop(result);
+
+        // Skipping doesn't leave stale data for next round.
+        let original = test_util::generate_original(3, 64, 123);
+        for shard in &original {
+            encoder.add_original_shard(shard).unwrap();
+        }
+        let result = encoder.encode().unwrap();
+        assert!(result.recovery_iter().any(|shard| shard != [0; 64]));
+    }
+
     // ============================================================
     // ROUNDTRIPS - TWO ROUNDS
 
@@ -446,7 +707,7 @@ mod tests {
     mod low_rate {
         use crate::{
             engine::NoSimd,
-            rate::{LowRate, Rate},
+            rate::{LowRate, Rate, RateDecoder, RateEncoder},
             Error,
         };
 
@@ -463,6 +724,32 @@ mod tests {
             );
         }
 
+        #[test]
+        fn decoder_work_count() {
+            for (original_count, recovery_count) in [(1, 1), (2, 3), (100, 1000), (1024, 3073)] {
+                let mut decoder = LowRate::<NoSimd>::decoder(
+                    original_count,
+                    recovery_count,
+                    64,
+                    NoSimd::new(),
+                    None,
+                )
+                .unwrap();
+                decoder.add_recovery_shard(0, [0; 64]).unwrap();
+                for index in 1..original_count {
+                    decoder.add_original_shard(index, [0; 64]).unwrap();
+                }
+
+                let (work, ..) = decoder.work.decode_begin().unwrap().unwrap();
+                assert_eq!(
+                    work.len(),
+                    LowRate::<NoSimd>::decoder_work_count(original_count, recovery_count)
+                );
+            }
+
+            assert_eq!(LowRate::<NoSimd>::decoder_work_count(61440, 4096), 0);
+        }
+
         #[test]
         fn encoder() {
             assert!(LowRate::<NoSimd>::encoder(4096, 61440, 64, NoSimd::new(), None).is_ok());
@@ -476,6 +763,31 @@ mod tests {
             );
         }
 
+        #[test]
+        fn encoder_work_count() {
+            for (original_count, recovery_count) in [(1, 1), (2, 3), (100, 1000), (1024, 3073)] {
+                let mut encoder = LowRate::<NoSimd>::encoder(
+                    original_count,
+                    recovery_count,
+                    64,
+                    NoSimd::new(),
+                    None,
+                )
+                .unwrap();
+                for _ in 0..original_count {
+                    encoder.add_original_shard([0; 64]).unwrap();
+                }
+
+                let (work, ..) = encoder.work.encode_begin().unwrap();
+                assert_eq!(
+                    work.len(),
+                    LowRate::<NoSimd>::encoder_work_count(original_count, recovery_count)
+                );
+            }
+
+            assert_eq!(LowRate::<NoSimd>::encoder_work_count(61440, 4096), 0);
+        }
+
         #[test]
         fn supports() {
             assert!(!LowRate::<NoSimd>::supports(0, 1));
@@ -497,6 +809,29 @@ mod tests {
                 Some(Error::InvalidShardSize { shard_bytes: 123 })
             );
 
+            for shard_bytes in [0, 1] {
+                assert_eq!(
+                    LowRate::<NoSimd>::validate(1, 1, shard_bytes).err(),
+                    Some(Error::InvalidShardSize { shard_bytes })
+                );
+            }
+
+            assert_eq!(
+                LowRate::<NoSimd>::validate(0, 1, 64).err(),
+                Some(Error::InvalidShardCount {
+                    original_count: 0,
+                    recovery_count: 1,
+                })
+            );
+
+            assert_eq!(
+                LowRate::<NoSimd>::validate(1, 0, 64).err(),
+                Some(Error::InvalidShardCount {
+                    original_count: 1,
+                    recovery_count: 0,
+                })
+            );
+
             assert!(LowRate::<NoSimd>::validate(4096, 61440, 64).is_ok());
 
             assert_eq!(
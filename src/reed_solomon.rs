@@ -1,7 +1,10 @@
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
 use crate::{
-    engine::DefaultEngine,
+    engine::{DefaultEngine, Engine},
     rate::{DefaultRate, DefaultRateDecoder, DefaultRateEncoder, Rate, RateDecoder, RateEncoder},
-    DecoderResult, EncoderResult, Error,
+    DecoderResult, EncoderResult, Error, ShardKind,
 };
 
 // ======================================================================
@@ -9,8 +12,13 @@ use crate::{
 
 /// Reed-Solomon encoder using [`DefaultEngine`] and [`DefaultRate`].
 ///
+/// [`Default`] creates encoder which isn't configured yet,
+/// it must be [`reset`] before it can be used.
+///
 /// [`DefaultEngine`]: crate::engine::DefaultEngine
-pub struct ReedSolomonEncoder(DefaultRateEncoder<DefaultEngine>);
+/// [`reset`]: ReedSolomonEncoder::reset
+#[derive(Debug)]
+pub struct ReedSolomonEncoder(Option<DefaultRateEncoder<DefaultEngine>>);
 
 impl ReedSolomonEncoder {
     /// Adds one original shard to the encoder.
@@ -18,9 +26,20 @@ impl ReedSolomonEncoder {
     /// Original shards have indexes `0..original_count` corresponding to the order
     /// in which they are added and these same indexes must be used when decoding.
     ///
+    /// Shard is copied into working space of the encoder immediately,
+    /// so it can be given either borrowed or owned (e.g. `Vec<u8>`)
+    /// and it doesn't need to be kept alive after this call.
+    ///
+    /// As index is given by the order of calls, an index can't be added
+    /// twice and there is no [`Error::DuplicateOriginalShardIndex`] here.
+    /// Instead every call after `original_count` shards returns
+    /// [`Error::TooManyOriginalShards`] without changing the encoder,
+    /// so it's always the failing call which was one too many and
+    /// already added shards can still be encoded.
+    ///
     /// See [basic usage](crate#basic-usage) for an example.
     pub fn add_original_shard<T: AsRef<[u8]>>(&mut self, original_shard: T) -> Result<(), Error> {
-        self.0.add_original_shard(original_shard)
+        self.inner_mut()?.add_original_shard(original_shard)
     }
 
     /// Encodes the added original shards returning [`EncoderResult`]
@@ -29,11 +48,89 @@ impl ReedSolomonEncoder {
     /// When returned [`EncoderResult`] is dropped the encoder is
     /// automatically [`reset`] and ready for new round of encoding.
     ///
-    /// See [basic usage](crate#basic-usage) for an example.
+    /// Encoding is done in-place in the working space, so the added
+    /// original shards are consumed. To encode same original shards again,
+    /// e.g. with different `recovery_count`, they must be added again:
+    ///
+    /// ```rust
+    /// use simd_erasure_core::ReedSolomonEncoder;
+    ///
+    /// let original = [[1u8; 64], [2u8; 64]];
+    ///
+    /// let mut encoder = ReedSolomonEncoder::new(2, 1, 64)?;
+    /// for recovery_count in [1, 3] {
+    ///     encoder.reset(2, recovery_count, 64)?;
+    ///     for shard in original {
+    ///         encoder.add_original_shard(shard)?;
+    ///     }
+    ///     assert_eq!(encoder.encode()?.recovery_iter().len(), recovery_count);
+    /// }
+    /// # Ok::<(), simd_erasure_core::Error>(())
+    /// ```
+    ///
+    /// See [basic usage](crate#basic-usage) for a full example.
     ///
     /// [`reset`]: ReedSolomonEncoder::reset
     pub fn encode(&mut self) -> Result<EncoderResult<'_>, Error> {
-        self.0.encode()
+        self.inner_mut()?.encode()
+    }
+
+    /// Like [`encode`] but also reports progress by calling `progress`
+    /// with `(completed_butterflies, total_butterflies)`
+    /// after each FFT/IFFT round.
+    ///
+    /// This is meant for showing progress of large encoding jobs.
+    /// Using [`encode`] is equally fast when progress isn't needed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simd_erasure_core::ReedSolomonEncoder;
+    ///
+    /// let mut encoder = ReedSolomonEncoder::new(2, 3, 64)?;
+    /// encoder.add_original_shard([1u8; 64])?;
+    /// encoder.add_original_shard([2u8; 64])?;
+    ///
+    /// let mut last = (0, 0);
+    /// let result = encoder.encode_with_progress(|completed, total| {
+    ///     last = (completed, total);
+    /// })?;
+    ///
+    /// assert_eq!(last.0, last.1);
+    /// assert_eq!(result.recovery_iter().len(), 3);
+    /// # Ok::<(), simd_erasure_core::Error>(())
+    /// ```
+    ///
+    /// [`encode`]: ReedSolomonEncoder::encode
+    pub fn encode_with_progress<F: FnMut(usize, usize)>(
+        &mut self,
+        progress: F,
+    ) -> Result<EncoderResult<'_>, Error> {
+        self.inner_mut()?.encode_with_progress(progress)
+    }
+
+    /// Like [`encode`] but also zeroes the added original shards
+    /// in the working space of the encoder.
+    ///
+    /// Original shards are copied into the working space and
+    /// after [`encode`] they, and intermediate results derived from them,
+    /// stay there until overwritten by the next round of encoding.
+    /// This returns generated recovery shards as owned `Vec`:s,
+    /// in order of their indexes, and zeroes the whole working space,
+    /// so nothing derived from original shards is left in it.
+    ///
+    /// Only the working space of the current configuration is zeroed,
+    /// so this doesn't cover e.g. copies left behind by re-allocation
+    /// in [`reset`] or the shards which were given to the encoder.
+    ///
+    /// [`encode`]: ReedSolomonEncoder::encode
+    /// [`reset`]: ReedSolomonEncoder::reset
+    pub fn encode_zeroing_input(&mut self) -> Result<Vec<Vec<u8>>, Error> {
+        let mut result = self.inner_mut()?.encode()?;
+        result.zero_input();
+        Ok((0..)
+            .map_while(|index| result.take_recovery(index))
+            .collect())
     }
 
     /// Creates new encoder with given configuration
@@ -45,13 +142,66 @@ impl ReedSolomonEncoder {
         recovery_count: usize,
         shard_bytes: usize,
     ) -> Result<Self, Error> {
-        Ok(Self(DefaultRateEncoder::new(
+        Ok(Self(Some(DefaultRateEncoder::new(
             original_count,
             recovery_count,
             shard_bytes,
             DefaultEngine::new(),
             None,
-        )?))
+        )?)))
+    }
+
+    /// Returns configured number of original shards.
+    pub fn original_count(&self) -> usize {
+        self.0
+            .as_ref()
+            .map_or(0, |inner| inner.work().original_count())
+    }
+
+    /// Returns number of original shards added
+    /// since this encoder was reset or encoding was completed.
+    pub fn original_received_count(&self) -> usize {
+        self.0
+            .as_ref()
+            .map_or(0, |inner| inner.work().original_received_count())
+    }
+
+    /// Returns copy of original shard `index`
+    /// or `None` if it hasn't been added yet.
+    ///
+    /// This is meant for inspecting or verifying added shards
+    /// before encoding. Original shards are consumed by [`encode`],
+    /// so after it this returns `None` until shards are added again.
+    ///
+    /// A copy is returned because the working space keeps last 64 bytes
+    /// of a shard in a different layout if `shard_bytes` isn't
+    /// a multiple of 64, so the shard isn't there as a contiguous slice.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simd_erasure_core::ReedSolomonEncoder;
+    ///
+    /// let mut encoder = ReedSolomonEncoder::new(2, 1, 66)?;
+    /// encoder.add_original_shard([1u8; 66])?;
+    ///
+    /// assert_eq!(encoder.original_shard(0), Some(vec![1; 66]));
+    /// assert_eq!(encoder.original_shard(1), None);
+    /// # Ok::<(), simd_erasure_core::Error>(())
+    /// ```
+    ///
+    /// [`encode`]: ReedSolomonEncoder::encode
+    pub fn original_shard(&self, index: usize) -> Option<Vec<u8>> {
+        self.0
+            .as_ref()
+            .and_then(|inner| inner.work().original_shard(index))
+    }
+
+    /// Returns configured number of recovery shards.
+    pub fn recovery_count(&self) -> usize {
+        self.0
+            .as_ref()
+            .map_or(0, |inner| inner.work().recovery_count())
     }
 
     /// Resets encoder to given configuration.
@@ -65,7 +215,19 @@ impl ReedSolomonEncoder {
         recovery_count: usize,
         shard_bytes: usize,
     ) -> Result<(), Error> {
-        self.0.reset(original_count, recovery_count, shard_bytes)
+        if let Some(inner) = &mut self.0 {
+            inner.reset(original_count, recovery_count, shard_bytes)
+        } else {
+            *self = Self::new(original_count, recovery_count, shard_bytes)?;
+            Ok(())
+        }
+    }
+
+    /// Returns configured shard size in bytes.
+    pub fn shard_bytes(&self) -> usize {
+        self.0
+            .as_ref()
+            .map_or(0, |inner| inner.work().shard_bytes())
     }
 
     /// Returns `true` if given `original_count` / `recovery_count`
@@ -84,13 +246,50 @@ impl ReedSolomonEncoder {
     }
 }
 
+// ======================================================================
+// ReedSolomonEncoder - IMPL Default
+
+/// Creates encoder which isn't configured yet.
+///
+/// [`reset`] must be called before the encoder can be used,
+/// until then methods which need a configuration return
+/// [`Error::NotConfigured`] and accessors return `0`.
+///
+/// [`reset`]: ReedSolomonEncoder::reset
+impl Default for ReedSolomonEncoder {
+    fn default() -> Self {
+        Self(None)
+    }
+}
+
+// ======================================================================
+// ReedSolomonEncoder - PRIVATE
+
+impl ReedSolomonEncoder {
+    fn inner_mut(&mut self) -> Result<&mut DefaultRateEncoder<DefaultEngine>, Error> {
+        self.0.as_mut().ok_or(Error::NotConfigured)
+    }
+}
+
 // ======================================================================
 // ReedSolomonDecoder - PUBLIC
 
 /// Reed-Solomon decoder using [`DefaultEngine`] and [`DefaultRate`].
 ///
+/// [`Default`] creates decoder which isn't configured yet,
+/// it must be [`reset`] before it can be used.
+///
+/// [`DefaultEngine`] selects the fastest engine supported by the CPU
+/// when decoder is created. To use a specific engine, or to switch
+/// engines between adding shards and decoding, use [`DefaultRateDecoder`]
+/// and its [`with_engine`] instead.
+///
 /// [`DefaultEngine`]: crate::engine::DefaultEngine
-pub struct ReedSolomonDecoder(DefaultRateDecoder<DefaultEngine>);
+/// [`DefaultRateDecoder`]: crate::rate::DefaultRateDecoder
+/// [`reset`]: ReedSolomonDecoder::reset
+/// [`with_engine`]: crate::rate::DefaultRateDecoder::with_engine
+#[derive(Debug)]
+pub struct ReedSolomonDecoder(Option<DefaultRateDecoder<DefaultEngine>>);
 
 impl ReedSolomonDecoder {
     /// Adds one original shard to the decoder.
@@ -104,7 +303,7 @@ impl ReedSolomonDecoder {
         index: usize,
         original_shard: T,
     ) -> Result<(), Error> {
-        self.0.add_original_shard(index, original_shard)
+        self.inner_mut()?.add_original_shard(index, original_shard)
     }
 
     /// Adds one recovery shard to the decoder.
@@ -118,7 +317,57 @@ impl ReedSolomonDecoder {
         index: usize,
         recovery_shard: T,
     ) -> Result<(), Error> {
-        self.0.add_recovery_shard(index, recovery_shard)
+        self.inner_mut()?.add_recovery_shard(index, recovery_shard)
+    }
+
+    /// Adds one shard of given `kind` to the decoder.
+    ///
+    /// This is same as [`add_original_shard`] or [`add_recovery_shard`]
+    /// depending on `kind`, which is convenient when kind of the shard
+    /// is known only at runtime, e.g. from [`ShardHeader`].
+    ///
+    /// [`add_original_shard`]: ReedSolomonDecoder::add_original_shard
+    /// [`add_recovery_shard`]: ReedSolomonDecoder::add_recovery_shard
+    /// [`ShardHeader`]: crate::wire::ShardHeader
+    pub fn add_shard<T: AsRef<[u8]>>(
+        &mut self,
+        kind: ShardKind,
+        index: usize,
+        shard: T,
+    ) -> Result<(), Error> {
+        self.inner_mut()?.add_shard(kind, index, shard)
+    }
+
+    /// Returns `true` if all original shards are available with shards
+    /// added so far, i.e. if every original shard either has been added
+    /// or can be restored by [`decode`].
+    ///
+    /// This is the case when at least `original_count` shards
+    /// have been added in total, so [`decode`] won't return
+    /// [`Error::NotEnoughShards`]. Returns `false` if decoder
+    /// hasn't been configured.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simd_erasure_core::ReedSolomonDecoder;
+    ///
+    /// let mut decoder = ReedSolomonDecoder::new(2, 1, 64)?;
+    ///
+    /// decoder.add_original_shard(0, [0; 64])?;
+    /// assert!(!decoder.all_originals_available());
+    ///
+    /// decoder.add_recovery_shard(0, [0; 64])?;
+    /// assert!(decoder.all_originals_available());
+    /// # Ok::<(), simd_erasure_core::Error>(())
+    /// ```
+    ///
+    /// [`decode`]: ReedSolomonDecoder::decode
+    pub fn all_originals_available(&self) -> bool {
+        let original_count = self.original_count();
+
+        original_count > 0
+            && self.original_received_count() + self.recovery_received_count() >= original_count
     }
 
     /// Decodes the added shards returning [`DecoderResult`]
@@ -127,11 +376,124 @@ impl ReedSolomonDecoder {
     /// When returned [`DecoderResult`] is dropped the decoder is
     /// automatically [`reset`] and ready for new round of decoding.
     ///
+    /// Decoding restores either all missing original shards or none:
+    /// with fewer than `original_count` shards in total every missing
+    /// original shard is undetermined, so [`Error::NotEnoughShards`]
+    /// is returned instead of a partial result.
+    /// Shards are also assumed to be correct, corrupted shards
+    /// can't be detected and must be left out by the caller,
+    /// e.g. by verifying a checksum before adding them.
+    ///
     /// See [basic usage](crate#basic-usage) for an example.
     ///
     /// [`reset`]: ReedSolomonDecoder::reset
     pub fn decode(&mut self) -> Result<DecoderResult<'_>, Error> {
-        self.0.decode()
+        self.inner_mut()?.decode()
+    }
+
+    /// Like [`decode`] but uses given `engine` instead of [`DefaultEngine`]
+    /// for decoding computations.
+    ///
+    /// Adding shards doesn't use the engine, so this allows adding them
+    /// on one machine and decoding with an engine chosen at decoding time,
+    /// e.g. [`NoSimd`] when [`DefaultEngine`] would pick a faster engine
+    /// not available where shards are added, or the other way around.
+    /// `engine` must use the default [`GfField`],
+    /// otherwise decoding gives wrong results.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simd_erasure_core::{engine::NoSimd, ReedSolomonDecoder};
+    ///
+    /// let original = [[1; 64], [2; 64], [3; 64]];
+    /// let recovery = simd_erasure_core::encode(3, 2, &original)?;
+    ///
+    /// let mut decoder = ReedSolomonDecoder::new(3, 2, 64)?;
+    /// decoder.add_original_shard(1, &original[1])?;
+    /// decoder.add_recovery_shard(0, &recovery[0])?;
+    /// decoder.add_recovery_shard(1, &recovery[1])?;
+    ///
+    /// let result = decoder.decode_with_engine(NoSimd::new())?;
+    /// assert_eq!(result.restored_original(0), Some(&original[0][..]));
+    /// assert_eq!(result.restored_original(2), Some(&original[2][..]));
+    /// # Ok::<(), simd_erasure_core::Error>(())
+    /// ```
+    ///
+    /// [`decode`]: ReedSolomonDecoder::decode
+    /// [`NoSimd`]: crate::engine::NoSimd
+    /// [`GfField`]: crate::engine::GfField
+    // Engine is taken by value like in `with_engine` of rate decoders.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn decode_with_engine<E2: Engine>(
+        &mut self,
+        engine: E2,
+    ) -> Result<DecoderResult<'_>, Error> {
+        self.inner_mut()?.decode_with_engine(&engine)
+    }
+
+    /// Like [`decode`] but also reports progress by calling `progress`
+    /// with `(completed_butterflies, total_butterflies)`
+    /// after each FFT/IFFT round.
+    ///
+    /// `progress` is not called at all if no original shards are missing.
+    ///
+    /// [`decode`]: ReedSolomonDecoder::decode
+    pub fn decode_with_progress<F: FnMut(usize, usize)>(
+        &mut self,
+        progress: F,
+    ) -> Result<DecoderResult<'_>, Error> {
+        self.inner_mut()?.decode_with_progress(progress)
+    }
+
+    /// Returns `true` if losing given number of original and recovery shards
+    /// still leaves enough shards to restore all original shards,
+    /// i.e. if `original_loss_count + recovery_loss_count <= recovery_count`.
+    ///
+    /// Returns `false` if loss counts are larger than configured shard counts
+    /// or if decoder hasn't been configured.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simd_erasure_core::ReedSolomonDecoder;
+    ///
+    /// let decoder = ReedSolomonDecoder::new(3, 2, 64)?;
+    ///
+    /// assert!(decoder.is_loss_recoverable(1, 1));
+    /// assert!(decoder.is_loss_recoverable(0, 2));
+    /// assert!(!decoder.is_loss_recoverable(2, 1));
+    /// # Ok::<(), simd_erasure_core::Error>(())
+    /// ```
+    pub fn is_loss_recoverable(
+        &self,
+        original_loss_count: usize,
+        recovery_loss_count: usize,
+    ) -> bool {
+        let original_count = self.original_count();
+        let recovery_count = self.recovery_count();
+
+        original_count > 0
+            && original_loss_count <= original_count
+            && recovery_loss_count <= recovery_count
+            && original_loss_count + recovery_loss_count <= recovery_count
+    }
+
+    /// Returns iterator over shards added to the decoder since
+    /// it was created or reset, as `(kind, index)` pairs.
+    ///
+    /// First original shards and then recovery shards
+    /// are returned, both ordered by their indexes.
+    pub fn iter_received(&self) -> impl Iterator<Item = (ShardKind, usize)> + '_ {
+        self.0.iter().flat_map(|inner| inner.work().iter_received())
+    }
+
+    /// Returns maximum number of original shards which can be lost
+    /// and still be restored, i.e. `min(original_count, recovery_count)`.
+    ///
+    /// This is `0` if decoder hasn't been configured.
+    pub fn max_recoverable_losses(&self) -> usize {
+        self.original_count().min(self.recovery_count())
     }
 
     /// Creates new decoder with given configuration
@@ -143,13 +505,43 @@ impl ReedSolomonDecoder {
         recovery_count: usize,
         shard_bytes: usize,
     ) -> Result<Self, Error> {
-        Ok(Self(DefaultRateDecoder::new(
+        Ok(Self(Some(DefaultRateDecoder::new(
             original_count,
             recovery_count,
             shard_bytes,
             DefaultEngine::new(),
             None,
-        )?))
+        )?)))
+    }
+
+    /// Returns configured number of original shards.
+    pub fn original_count(&self) -> usize {
+        self.0
+            .as_ref()
+            .map_or(0, |inner| inner.work().original_count())
+    }
+
+    /// Returns number of original shards added
+    /// since this decoder was reset or decoding was completed.
+    pub fn original_received_count(&self) -> usize {
+        self.0
+            .as_ref()
+            .map_or(0, |inner| inner.work().original_received_count())
+    }
+
+    /// Returns configured number of recovery shards.
+    pub fn recovery_count(&self) -> usize {
+        self.0
+            .as_ref()
+            .map_or(0, |inner| inner.work().recovery_count())
+    }
+
+    /// Returns number of recovery shards added
+    /// since this decoder was reset or decoding was completed.
+    pub fn recovery_received_count(&self) -> usize {
+        self.0
+            .as_ref()
+            .map_or(0, |inner| inner.work().recovery_received_count())
     }
 
     /// Resets decoder to given configuration.
@@ -163,7 +555,19 @@ impl ReedSolomonDecoder {
         recovery_count: usize,
         shard_bytes: usize,
     ) -> Result<(), Error> {
-        self.0.reset(original_count, recovery_count, shard_bytes)
+        if let Some(inner) = &mut self.0 {
+            inner.reset(original_count, recovery_count, shard_bytes)
+        } else {
+            *self = Self::new(original_count, recovery_count, shard_bytes)?;
+            Ok(())
+        }
+    }
+
+    /// Returns configured shard size in bytes.
+    pub fn shard_bytes(&self) -> usize {
+        self.0
+            .as_ref()
+            .map_or(0, |inner| inner.work().shard_bytes())
     }
 
     /// Returns `true` if given `original_count` / `recovery_count`
@@ -180,6 +584,70 @@ impl ReedSolomonDecoder {
     pub fn supports(original_count: usize, recovery_count: usize) -> bool {
         DefaultRate::<DefaultEngine>::supports(original_count, recovery_count)
     }
+
+    /// Like [`decode`] but returns `Ok(None)` instead of
+    /// [`Error::NotEnoughShards`] when more shards are needed.
+    ///
+    /// This is convenient when decoding is attempted after each received
+    /// shard, as then other errors can be propagated with `?` as usual.
+    /// Nothing is reset when `Ok(None)` is returned,
+    /// so already added shards are kept.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use simd_erasure_core::ReedSolomonDecoder;
+    ///
+    /// let original = [[1; 64], [2; 64], [3; 64]];
+    /// let recovery = simd_erasure_core::encode(3, 2, &original)?;
+    ///
+    /// let mut decoder = ReedSolomonDecoder::new(3, 2, 64)?;
+    ///
+    /// decoder.add_recovery_shard(0, &recovery[0])?;
+    /// assert!(decoder.try_decode()?.is_none());
+    ///
+    /// decoder.add_recovery_shard(1, &recovery[1])?;
+    /// assert!(decoder.try_decode()?.is_none());
+    ///
+    /// decoder.add_original_shard(2, &original[2])?;
+    /// let result = decoder.try_decode()?.unwrap();
+    /// assert_eq!(result.restored_original(0), Some(&original[0][..]));
+    /// # Ok::<(), simd_erasure_core::Error>(())
+    /// ```
+    ///
+    /// [`decode`]: ReedSolomonDecoder::decode
+    pub fn try_decode(&mut self) -> Result<Option<DecoderResult<'_>>, Error> {
+        match self.decode() {
+            Ok(result) => Ok(Some(result)),
+            Err(Error::NotEnoughShards { .. }) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+// ======================================================================
+// ReedSolomonDecoder - IMPL Default
+
+/// Creates decoder which isn't configured yet.
+///
+/// [`reset`] must be called before the decoder can be used,
+/// until then methods which need a configuration return
+/// [`Error::NotConfigured`] and accessors return `0`.
+///
+/// [`reset`]: ReedSolomonDecoder::reset
+impl Default for ReedSolomonDecoder {
+    fn default() -> Self {
+        Self(None)
+    }
+}
+
+// ======================================================================
+// ReedSolomonDecoder - PRIVATE
+
+impl ReedSolomonDecoder {
+    fn inner_mut(&mut self) -> Result<&mut DefaultRateDecoder<DefaultEngine>, Error> {
+        self.0.as_mut().ok_or(Error::NotConfigured)
+    }
 }
 
 // ======================================================================
@@ -189,12 +657,12 @@ impl ReedSolomonDecoder {
 mod tests {
     use alloc::collections::BTreeMap;
     #[cfg(not(feature = "std"))]
-    use alloc::vec::Vec;
+    use alloc::{format, vec::Vec};
 
     use fixedbitset::FixedBitSet;
 
     use super::*;
-    use crate::test_util;
+    use crate::{engine::NoSimd, rate, test_util};
 
     // ============================================================
     // HELPERS
@@ -272,7 +740,517 @@ mod tests {
         );
     }
 
-    // ==================================================
+    // ============================================================
+    // PROGRESS
+
+    fn assert_progress(calls: &[(usize, usize)]) {
+        assert!(!calls.is_empty());
+
+        let total = calls[0].1;
+        let mut previous = 0;
+        for (completed, got_total) in calls {
+            assert_eq!(*got_total, total);
+            assert!(*completed > previous);
+            previous = *completed;
+        }
+        assert_eq!(previous, total);
+    }
+
+    #[test]
+    fn encode_decode_with_progress() {
+        for (original_count, recovery_count) in [(2, 3), (3, 2), (1000, 100), (100, 1000)] {
+            let original = test_util::generate_original(original_count, 64, 0);
+
+            let mut encoder = ReedSolomonEncoder::new(original_count, recovery_count, 64).unwrap();
+            for original in &original {
+                encoder.add_original_shard(original).unwrap();
+            }
+
+            let mut calls = Vec::new();
+            let result = encoder
+                .encode_with_progress(|completed, total| calls.push((completed, total)))
+                .unwrap();
+            let recovery: Vec<_> = result.recovery_iter().map(<[u8]>::to_vec).collect();
+            drop(result);
+            assert_progress(&calls);
+
+            let mut decoder = ReedSolomonDecoder::new(original_count, recovery_count, 64).unwrap();
+            let lost = core::cmp::min(original_count, recovery_count);
+            for i in lost..original_count {
+                decoder.add_original_shard(i, &original[i]).unwrap();
+            }
+            for (i, recovery) in recovery.iter().enumerate().take(lost) {
+                decoder.add_recovery_shard(i, recovery).unwrap();
+            }
+
+            let mut calls = Vec::new();
+            let result = decoder
+                .decode_with_progress(|completed, total| calls.push((completed, total)))
+                .unwrap();
+            for (i, restored) in result.restored_original_iter() {
+                assert_eq!(restored, original[i]);
+            }
+            assert_progress(&calls);
+        }
+    }
+
+    #[test]
+    fn decode_with_progress_nothing_to_do() {
+        let mut decoder = ReedSolomonDecoder::new(1, 1, 64).unwrap();
+        decoder.add_original_shard(0, [0u8; 64]).unwrap();
+
+        let mut called = false;
+        decoder.decode_with_progress(|_, _| called = true).unwrap();
+        assert!(!called);
+    }
+
+    #[test]
+    fn butterfly_count_is_progress_total() {
+        for (original_count, recovery_count) in [
+            (1, 1),
+            (2, 3),
+            (3, 2),
+            (100, 1000),
+            (1000, 100),
+            (1024, 1024),
+            (1025, 1025),
+            (3000, 30000),
+        ] {
+            let mut encoder = ReedSolomonEncoder::new(original_count, recovery_count, 64).unwrap();
+            for _ in 0..original_count {
+                encoder.add_original_shard([0u8; 64]).unwrap();
+            }
+
+            let mut total = 0;
+            encoder.encode_with_progress(|_, t| total = t).unwrap();
+
+            assert_eq!(
+                rate::butterfly_count(original_count, recovery_count),
+                total as u64
+            );
+        }
+    }
+
+    // ============================================================
+    // add_shard
+
+    #[test]
+    fn add_shard() {
+        let original = test_util::generate_original(3, 1024, 0);
+        let recovery = crate::encode(3, 2, &original).unwrap();
+
+        let mut decoder = ReedSolomonDecoder::new(3, 2, 1024).unwrap();
+        decoder.add_original_shard(1, &original[1]).unwrap();
+        decoder.add_recovery_shard(0, &recovery[0]).unwrap();
+        decoder.add_recovery_shard(1, &recovery[1]).unwrap();
+        let expected: Vec<_> = decoder
+            .decode()
+            .unwrap()
+            .restored_original_iter()
+            .map(|(index, shard)| (index, shard.to_vec()))
+            .collect();
+
+        let mut decoder = ReedSolomonDecoder::new(3, 2, 1024).unwrap();
+        decoder
+            .add_shard(ShardKind::Recovery, 1, &recovery[1])
+            .unwrap();
+        decoder
+            .add_shard(ShardKind::Original, 1, &original[1])
+            .unwrap();
+        decoder
+            .add_shard(ShardKind::Recovery, 0, &recovery[0])
+            .unwrap();
+
+        assert_eq!(
+            decoder.add_shard(ShardKind::Original, 1, &original[1]),
+            Err(Error::DuplicateOriginalShardIndex { index: 1 })
+        );
+        assert_eq!(
+            decoder.add_shard(ShardKind::Recovery, 2, &recovery[0]),
+            Err(Error::InvalidRecoveryShardIndex {
+                recovery_count: 2,
+                index: 2
+            })
+        );
+
+        let result = decoder.decode().unwrap();
+        assert!(result.restored_original_iter().eq(expected
+            .iter()
+            .map(|(index, shard)| (*index, shard.as_slice()))));
+        assert_eq!(expected[0], (0, original[0].clone()));
+        assert_eq!(expected[1], (2, original[2].clone()));
+    }
+
+    // ============================================================
+    // add_original_shard - OWNED
+
+    #[test]
+    fn add_original_shard_owned() {
+        let mut encoder = ReedSolomonEncoder::new(2, 3, 1024).unwrap();
+
+        for original in test_util::generate_original(2, 1024, 123) {
+            encoder.add_original_shard(original).unwrap();
+        }
+
+        let result = encoder.encode().unwrap();
+        test_util::assert_hash(result.recovery_iter(), test_util::LOW_2_3);
+    }
+
+    // ============================================================
+    // add_original_shard
+
+    #[test]
+    fn add_original_shard_too_many_keeps_added_shards() {
+        let original = test_util::generate_original(3, 1024, 0);
+        let expected = crate::encode(3, 2, &original).unwrap();
+
+        let mut encoder = ReedSolomonEncoder::new(3, 2, 1024).unwrap();
+        for shard in &original {
+            encoder.add_original_shard(shard).unwrap();
+        }
+
+        assert_eq!(
+            encoder.add_original_shard(&original[0]),
+            Err(Error::TooManyOriginalShards { original_count: 3 })
+        );
+        assert_eq!(encoder.original_received_count(), 3);
+
+        let result = encoder.encode().unwrap();
+        assert!(result
+            .recovery_iter()
+            .eq(expected.iter().map(Vec::as_slice)));
+    }
+
+    // ============================================================
+    // encode_zeroing_input
+
+    #[test]
+    fn encode_zeroing_input() {
+        let original = test_util::generate_original(3, 1000, 0);
+
+        let mut encoder = ReedSolomonEncoder::new(3, 2, 1000).unwrap();
+        for shard in &original {
+            encoder.add_original_shard(shard).unwrap();
+        }
+        let expected: Vec<_> = encoder
+            .encode()
+            .unwrap()
+            .recovery_iter()
+            .map(<[u8]>::to_vec)
+            .collect();
+
+        // second round works as usual
+        for _ in 0..2 {
+            for shard in &original {
+                encoder.add_original_shard(shard).unwrap();
+            }
+            let recovery = encoder.encode_zeroing_input().unwrap();
+            assert_eq!(recovery, expected);
+        }
+
+        // recovery shards are kept over next round
+        for shard in &original {
+            encoder.add_original_shard(shard).unwrap();
+        }
+        let recovery = encoder.encode_zeroing_input().unwrap();
+        for shard in &original {
+            encoder
+                .add_original_shard(shard.iter().map(|x| !x).collect::<Vec<_>>())
+                .unwrap();
+        }
+        assert_ne!(encoder.encode().unwrap().recovery(0).unwrap(), expected[0]);
+        assert_eq!(recovery, expected);
+
+        assert_eq!(
+            ReedSolomonEncoder::default().encode_zeroing_input().err(),
+            Some(Error::NotConfigured)
+        );
+    }
+
+    // ============================================================
+    // Debug
+
+    #[test]
+    fn debug() {
+        let encoder = ReedSolomonEncoder::new(3, 2, 64).unwrap();
+        let debug = format!("{encoder:?}");
+        assert!(debug.starts_with("ReedSolomonEncoder(Some(DefaultRateEncoder(High(HighRateEncoder { engine: DefaultEngine("));
+        assert!(debug.contains("original_count: 3"));
+
+        let decoder = ReedSolomonDecoder::new(2, 5, 64).unwrap();
+        let debug = format!("{decoder:?}");
+        assert!(debug.starts_with("ReedSolomonDecoder(Some(DefaultRateDecoder(Low(LowRateDecoder { engine: DefaultEngine("));
+        assert!(debug.contains("recovery_count: 5"));
+
+        assert_eq!(
+            format!("{:?}", ReedSolomonEncoder::default()),
+            "ReedSolomonEncoder(None)"
+        );
+        assert_eq!(
+            format!("{:?}", ReedSolomonDecoder::default()),
+            "ReedSolomonDecoder(None)"
+        );
+    }
+
+    // ============================================================
+    // default
+
+    #[test]
+    fn default_not_configured() {
+        let mut encoder = ReedSolomonEncoder::default();
+        assert_eq!(encoder.original_count(), 0);
+        assert_eq!(encoder.recovery_count(), 0);
+        assert_eq!(encoder.shard_bytes(), 0);
+        assert_eq!(
+            encoder.add_original_shard([0; 64]),
+            Err(Error::NotConfigured)
+        );
+        assert_eq!(encoder.encode().err(), Some(Error::NotConfigured));
+
+        let mut decoder = ReedSolomonDecoder::default();
+        assert_eq!(decoder.original_count(), 0);
+        assert_eq!(decoder.recovery_count(), 0);
+        assert_eq!(decoder.shard_bytes(), 0);
+        assert_eq!(decoder.iter_received().count(), 0);
+        assert_eq!(
+            decoder.add_original_shard(0, [0; 64]),
+            Err(Error::NotConfigured)
+        );
+        assert_eq!(
+            decoder.add_recovery_shard(0, [0; 64]),
+            Err(Error::NotConfigured)
+        );
+        assert_eq!(decoder.decode().err(), Some(Error::NotConfigured));
+
+        // invalid configuration keeps it unconfigured
+        assert_eq!(
+            decoder.reset(0, 1, 64),
+            Err(Error::InvalidShardCount {
+                original_count: 0,
+                recovery_count: 1,
+            })
+        );
+        assert_eq!(decoder.decode().err(), Some(Error::NotConfigured));
+    }
+
+    #[test]
+    fn default_then_reset() {
+        let mut encoder = ReedSolomonEncoder::default();
+        encoder.reset(2, 1, 64).unwrap();
+        encoder.add_original_shard([1; 64]).unwrap();
+        encoder.add_original_shard([2; 64]).unwrap();
+        let recovery = encoder.encode().unwrap().recovery(0).unwrap().to_vec();
+
+        let mut decoder = ReedSolomonDecoder::default();
+        decoder.reset(2, 1, 64).unwrap();
+        decoder.add_original_shard(1, [2; 64]).unwrap();
+        decoder.add_recovery_shard(0, &recovery).unwrap();
+        let result = decoder.decode().unwrap();
+        assert_eq!(result.restored_original(0), Some(&[1; 64][..]));
+    }
+
+    // ============================================================
+    // original_count / recovery_count / shard_bytes
+
+    #[test]
+    fn configuration_accessors() {
+        let mut encoder = ReedSolomonEncoder::new(3, 2, 1024).unwrap();
+        assert_eq!(encoder.original_count(), 3);
+        assert_eq!(encoder.recovery_count(), 2);
+        assert_eq!(encoder.shard_bytes(), 1024);
+
+        // reset from high rate to low rate
+        encoder.reset(2, 5, 64).unwrap();
+        assert_eq!(encoder.original_count(), 2);
+        assert_eq!(encoder.recovery_count(), 5);
+        assert_eq!(encoder.shard_bytes(), 64);
+
+        let mut decoder = ReedSolomonDecoder::new(3, 2, 1024).unwrap();
+        assert_eq!(decoder.original_count(), 3);
+        assert_eq!(decoder.recovery_count(), 2);
+        assert_eq!(decoder.shard_bytes(), 1024);
+
+        decoder.reset(2, 5, 64).unwrap();
+        assert_eq!(decoder.original_count(), 2);
+        assert_eq!(decoder.recovery_count(), 5);
+        assert_eq!(decoder.shard_bytes(), 64);
+    }
+
+    // ============================================================
+    // original_shard
+
+    #[test]
+    fn original_shard() {
+        assert_eq!(ReedSolomonEncoder::default().original_shard(0), None);
+
+        for (original_count, recovery_count) in [(3, 2), (2, 5)] {
+            for shard_bytes in [64, 100, 130] {
+                let original = test_util::generate_original(original_count, shard_bytes, 0);
+                let mut encoder =
+                    ReedSolomonEncoder::new(original_count, recovery_count, shard_bytes).unwrap();
+
+                for (index, shard) in original.iter().enumerate() {
+                    encoder.add_original_shard(shard).unwrap();
+                    assert_eq!(encoder.original_shard(index).as_ref(), Some(shard));
+                    assert_eq!(encoder.original_shard(index + 1), None);
+                }
+                for (index, shard) in original.iter().enumerate() {
+                    assert_eq!(encoder.original_shard(index).as_ref(), Some(shard));
+                }
+
+                drop(encoder.encode().unwrap());
+                assert_eq!(encoder.original_shard(0), None);
+            }
+        }
+    }
+
+    // ============================================================
+    // original_received_count / recovery_received_count
+
+    #[test]
+    fn received_counts() {
+        let mut encoder = ReedSolomonEncoder::new(3, 2, 64).unwrap();
+        for count in 1..=3 {
+            encoder.add_original_shard([0; 64]).unwrap();
+            assert_eq!(encoder.original_received_count(), count);
+        }
+        drop(encoder.encode().unwrap());
+        assert_eq!(encoder.original_received_count(), 0);
+
+        let mut decoder = ReedSolomonDecoder::new(3, 2, 64).unwrap();
+        for index in 0..2 {
+            decoder.add_recovery_shard(index, [0; 64]).unwrap();
+            assert_eq!(decoder.recovery_received_count(), index + 1);
+        }
+        for index in 0..2 {
+            decoder.add_original_shard(index, [0; 64]).unwrap();
+            assert_eq!(decoder.original_received_count(), index + 1);
+            assert_eq!(decoder.recovery_received_count(), 2);
+        }
+
+        decoder.reset(3, 2, 64).unwrap();
+        assert_eq!(decoder.original_received_count(), 0);
+        assert_eq!(decoder.recovery_received_count(), 0);
+
+        assert_eq!(ReedSolomonEncoder::default().original_received_count(), 0);
+        assert_eq!(ReedSolomonDecoder::default().recovery_received_count(), 0);
+    }
+
+    // ============================================================
+    // is_loss_recoverable / max_recoverable_losses
+
+    #[test]
+    fn recoverable_losses() {
+        let decoder = ReedSolomonDecoder::new(3, 2, 64).unwrap();
+        assert_eq!(decoder.max_recoverable_losses(), 2);
+
+        for (original_loss_count, recovery_loss_count, expected) in [
+            (0, 0, true),
+            (2, 0, true),
+            (1, 1, true),
+            (0, 2, true),
+            (3, 0, false),
+            (2, 1, false),
+            (0, 3, false),
+            (usize::MAX, 0, false),
+        ] {
+            assert_eq!(
+                decoder.is_loss_recoverable(original_loss_count, recovery_loss_count),
+                expected
+            );
+        }
+
+        let decoder = ReedSolomonDecoder::new(2, 5, 64).unwrap();
+        assert_eq!(decoder.max_recoverable_losses(), 2);
+        assert!(decoder.is_loss_recoverable(2, 3));
+        assert!(!decoder.is_loss_recoverable(2, 4));
+
+        let decoder = ReedSolomonDecoder::default();
+        assert_eq!(decoder.max_recoverable_losses(), 0);
+        assert!(!decoder.is_loss_recoverable(0, 0));
+    }
+
+    // ============================================================
+    // iter_received
+
+    #[test]
+    fn iter_received() {
+        let mut decoder = ReedSolomonDecoder::new(3, 2, 64).unwrap();
+        decoder.add_recovery_shard(0, [0; 64]).unwrap();
+        decoder.add_original_shard(1, [0; 64]).unwrap();
+
+        assert_eq!(
+            decoder.iter_received().collect::<Vec<_>>(),
+            [(ShardKind::Original, 1), (ShardKind::Recovery, 0)]
+        );
+
+        decoder.reset(3, 2, 64).unwrap();
+        assert_eq!(decoder.iter_received().count(), 0);
+    }
+
+    // ============================================================
+    // new
+
+    #[test]
+    fn new_errors() {
+        for shard_bytes in [0, 1] {
+            let expected = Error::InvalidShardSize { shard_bytes };
+            assert_eq!(
+                ReedSolomonEncoder::new(2, 3, shard_bytes).err(),
+                Some(expected)
+            );
+            assert_eq!(
+                ReedSolomonDecoder::new(2, 3, shard_bytes).err(),
+                Some(expected)
+            );
+        }
+
+        for (original_count, recovery_count) in [(0, 1), (1, 0), (0, 0)] {
+            let expected = Error::InvalidShardCount {
+                original_count,
+                recovery_count,
+            };
+            assert_eq!(
+                ReedSolomonEncoder::new(original_count, recovery_count, 64).err(),
+                Some(expected)
+            );
+            assert_eq!(
+                ReedSolomonDecoder::new(original_count, recovery_count, 64).err(),
+                Some(expected)
+            );
+        }
+    }
+
+    #[test]
+    fn reset_errors() {
+        let mut encoder = ReedSolomonEncoder::new(2, 3, 64).unwrap();
+        assert_eq!(
+            encoder.reset(0, 3, 64),
+            Err(Error::InvalidShardCount {
+                original_count: 0,
+                recovery_count: 3,
+            })
+        );
+        assert_eq!(
+            encoder.reset(2, 3, 1),
+            Err(Error::InvalidShardSize { shard_bytes: 1 })
+        );
+
+        let mut decoder = ReedSolomonDecoder::new(2, 3, 64).unwrap();
+        assert_eq!(
+            decoder.reset(2, 0, 64),
+            Err(Error::InvalidShardCount {
+                original_count: 2,
+                recovery_count: 0,
+            })
+        );
+        assert_eq!(
+            decoder.reset(2, 3, 0),
+            Err(Error::InvalidShardSize { shard_bytes: 0 })
+        );
+    }
+
+    // ============================================================
     // supports
 
     #[test]
@@ -283,4 +1261,92 @@ mod tests {
         assert!(ReedSolomonDecoder::supports(4096, 61440));
         assert!(ReedSolomonDecoder::supports(61440, 4096));
     }
+
+    // ============================================================
+    // all_originals_available
+
+    #[test]
+    fn all_originals_available() {
+        let original = test_util::generate_original(3, 1024, 0);
+        let recovery = crate::encode(3, 2, &original).unwrap();
+
+        let mut decoder = ReedSolomonDecoder::new(3, 2, 1024).unwrap();
+        assert!(!decoder.all_originals_available());
+
+        decoder.add_original_shard(1, &original[1]).unwrap();
+        decoder.add_recovery_shard(0, &recovery[0]).unwrap();
+        assert!(!decoder.all_originals_available());
+
+        decoder.add_recovery_shard(1, &recovery[1]).unwrap();
+        assert!(decoder.all_originals_available());
+        decoder.decode().unwrap();
+
+        // Decoder is reset after decoding.
+        assert!(!decoder.all_originals_available());
+
+        for (index, shard) in original.iter().enumerate() {
+            decoder.add_original_shard(index, shard).unwrap();
+        }
+        assert!(decoder.all_originals_available());
+
+        assert!(!ReedSolomonDecoder::default().all_originals_available());
+    }
+
+    // ============================================================
+    // decode_with_engine
+
+    #[test]
+    fn decode_with_engine() {
+        for (original_count, recovery_count) in [(3, 2), (2, 3)] {
+            let original = test_util::generate_original(original_count, 1024, 0);
+            let recovery = crate::encode(original_count, recovery_count, &original).unwrap();
+
+            let mut decoder =
+                ReedSolomonDecoder::new(original_count, recovery_count, 1024).unwrap();
+            for (index, shard) in original.iter().enumerate().skip(2) {
+                decoder.add_original_shard(index, shard).unwrap();
+            }
+            decoder.add_recovery_shard(0, &recovery[0]).unwrap();
+            decoder.add_recovery_shard(1, &recovery[1]).unwrap();
+
+            let result = decoder.decode_with_engine(NoSimd::new()).unwrap();
+            assert_eq!(result.restored_original(0).unwrap(), original[0]);
+            assert_eq!(result.restored_original(1).unwrap(), original[1]);
+        }
+
+        assert_eq!(
+            ReedSolomonDecoder::default()
+                .decode_with_engine(NoSimd::new())
+                .err(),
+            Some(Error::NotConfigured)
+        );
+    }
+
+    // ============================================================
+    // try_decode
+
+    #[test]
+    fn try_decode() {
+        let original = test_util::generate_original(3, 1024, 0);
+        let recovery = crate::encode(3, 2, &original).unwrap();
+
+        let mut decoder = ReedSolomonDecoder::new(3, 2, 1024).unwrap();
+        assert!(decoder.try_decode().unwrap().is_none());
+
+        decoder.add_original_shard(1, &original[1]).unwrap();
+        decoder.add_recovery_shard(0, &recovery[0]).unwrap();
+        assert!(decoder.try_decode().unwrap().is_none());
+        assert_eq!(decoder.original_received_count(), 1);
+        assert_eq!(decoder.recovery_received_count(), 1);
+
+        decoder.add_recovery_shard(1, &recovery[1]).unwrap();
+        let result = decoder.try_decode().unwrap().unwrap();
+        assert_eq!(result.restored_original(0).unwrap(), original[0]);
+        assert_eq!(result.restored_original(2).unwrap(), original[2]);
+
+        assert_eq!(
+            ReedSolomonDecoder::default().try_decode().err(),
+            Some(Error::NotConfigured)
+        );
+    }
 }
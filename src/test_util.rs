@@ -380,6 +380,23 @@ macro_rules! test_rate_encoder_errors {
             );
         }
 
+        #[test]
+        fn invalid_state_after_leaked_result() {
+            let mut encoder = $Encoder::new(1, 1, 64, NoSimd::new(), None).unwrap();
+            encoder.add_original_shard([0; 64]).unwrap();
+            core::mem::forget(encoder.encode().unwrap());
+
+            assert_eq!(
+                encoder.add_original_shard([0; 64]),
+                Err(Error::InvalidState),
+            );
+            assert_eq!(encoder.encode().err(), Some(Error::InvalidState));
+
+            encoder.reset(1, 1, 64).unwrap();
+            encoder.add_original_shard([0; 64]).unwrap();
+            assert!(encoder.encode().is_ok());
+        }
+
         #[test]
         fn invalid_shard_size_in_new() {
             assert_eq!(
@@ -420,10 +437,10 @@ macro_rules! test_rate_encoder_errors {
         }
 
         #[test]
-        fn unsupported_shard_count_in_new() {
+        fn invalid_shard_count_in_new() {
             assert_eq!(
                 $Encoder::new(0, 1, 64, NoSimd::new(), None).err(),
-                Some(Error::UnsupportedShardCount {
+                Some(Error::InvalidShardCount {
                     original_count: 0,
                     recovery_count: 1,
                 }),
@@ -431,11 +448,11 @@ macro_rules! test_rate_encoder_errors {
         }
 
         #[test]
-        fn unsupported_shard_count_in_reset() {
+        fn invalid_shard_count_in_reset() {
             let mut encoder = $Encoder::new(1, 1, 64, NoSimd::new(), None).unwrap();
             assert_eq!(
                 encoder.reset(0, 1, 64),
-                Err(Error::UnsupportedShardCount {
+                Err(Error::InvalidShardCount {
                     original_count: 0,
                     recovery_count: 1,
                 }),
@@ -548,10 +565,10 @@ macro_rules! test_rate_decoder_errors {
         }
 
         #[test]
-        fn unsupported_shard_count_in_new() {
+        fn invalid_shard_count_in_new() {
             assert_eq!(
                 $Decoder::new(0, 1, 64, NoSimd::new(), None).err(),
-                Some(Error::UnsupportedShardCount {
+                Some(Error::InvalidShardCount {
                     original_count: 0,
                     recovery_count: 1,
                 }),
@@ -559,11 +576,11 @@ macro_rules! test_rate_decoder_errors {
         }
 
         #[test]
-        fn unsupported_shard_count_in_reset() {
+        fn invalid_shard_count_in_reset() {
             let mut decoder = $Decoder::new(1, 1, 64, NoSimd::new(), None).unwrap();
             assert_eq!(
                 decoder.reset(0, 1, 64),
-                Err(Error::UnsupportedShardCount {
+                Err(Error::InvalidShardCount {
                     original_count: 0,
                     recovery_count: 1,
                 }),
@@ -797,6 +814,10 @@ pub(crate) const HIGH_3_2: &str =
 pub(crate) const HIGH_3_2_232: &str =
     "2ee88d495ae1fff216f2865dbbdda2e1a051c5d98c7117a2a0b2ebcdfb57cd33";
 
+// 3 original ; 2 recovery ; 132 seed ; field 0x1100B
+pub(crate) const HIGH_3_2_FIELD_1100B: &str =
+    "cf7c41e5a071db3a065bce721a3dd7d47612c909558fe07046e016abeb7327a1";
+
 // 5 original ; 2 recovery ; 152 seed
 pub(crate) const HIGH_5_2: &str =
     "5387208d6756e3e79558a9b9ddebe0439eb3b08eec2393d4acafce6fc5332683";
@@ -829,6 +850,10 @@ pub(crate) const LOW_2_3: &str = "f682a6c87c2bcd3e0feddbeff5c34f9d14026b78c44e5f
 pub(crate) const LOW_2_3_223: &str =
     "2dc25a5dc42b2d1f94a80489e9f357a48f011f931cdac3ed7c85e2abb07063a2";
 
+// 2 original ; 3 recovery ; 123 seed ; field 0x1100B
+pub(crate) const LOW_2_3_FIELD_1100B: &str =
+    "9d1ee3bd648fb09641d0bb30c370d2d3e66e07de11d68f031e6a4f36f06f338e";
+
 // 2 original ; 5 recovery ; 125 seed
 pub(crate) const LOW_2_5: &str = "24449ae058f54a33b3b7ee568761e68e36bd7171ee2a3271a0fbd2f07ac65a7c";
 
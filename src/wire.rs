@@ -0,0 +1,281 @@
+//! Wire format for transmitting shards.
+//!
+//! Each shard is prefixed with a fixed-size [`ShardHeader`] which
+//! tells what the shard is, so that receiver can give it to decoder
+//! without any other metadata.
+//!
+//! Header is [`HEADER_BYTES`] bytes, all values are little-endian:
+//!
+//! | Bytes    | Value                                                      |
+//! | -------- | ---------------------------------------------------------- |
+//! | `0..4`   | `index`, highest bit set if [`ShardKind::Recovery`]        |
+//! | `4..8`   | `original_count`                                           |
+//! | `8..12`  | `recovery_count`                                           |
+//! | `12..16` | `shard_bytes`                                              |
+//! | `16..20` | `checksum`, CRC-32C of bytes `0..16`                       |
+//!
+//! # Examples
+//!
+//! ```rust
+//! use simd_erasure_core::{
+//!     wire::{self, ShardHeader},
+//!     ShardKind,
+//! };
+//!
+//! let header = ShardHeader {
+//!     kind: ShardKind::Recovery,
+//!     index: 1,
+//!     original_count: 3,
+//!     recovery_count: 2,
+//!     shard_bytes: 64,
+//!     checksum: 0,
+//! };
+//!
+//! let bytes = wire::encode_header(&header);
+//! let decoded = wire::decode_header(&bytes).unwrap();
+//!
+//! assert_eq!(decoded.index, 1);
+//! assert_eq!(decoded.kind, ShardKind::Recovery);
+//! assert!(decoded.validate_shard(&[0; 64]));
+//! ```
+
+use core::fmt;
+
+use crate::ShardKind;
+
+// ======================================================================
+// CONST - PUBLIC
+
+/// Size of encoded [`ShardHeader`] in bytes.
+pub const HEADER_BYTES: usize = 20;
+
+// ======================================================================
+// CONST - PRIVATE
+
+// CRC-32C (Castagnoli) polynomial in reversed bit order.
+const CRC32C_POLYNOMIAL: u32 = 0x82F6_3B78;
+
+const RECOVERY_BIT: u32 = 1 << 31;
+
+// ======================================================================
+// ShardHeader - PUBLIC
+
+/// Metadata of a single shard, see [module documentation](self)
+/// for the encoded format.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ShardHeader {
+    /// Whether shard is an original or a recovery shard.
+    pub kind: ShardKind,
+    /// Index of shard among shards of its kind.
+    ///
+    /// Highest bit is used for `kind` so this must be less than `2^31`.
+    pub index: u32,
+    /// Number of original shards.
+    pub original_count: u32,
+    /// Number of recovery shards.
+    pub recovery_count: u32,
+    /// Shard size in bytes.
+    pub shard_bytes: u32,
+    /// CRC-32C of the other header fields in encoded form.
+    ///
+    /// This is set by [`decode_header`] and ignored by [`encode_header`]
+    /// which always calculates it.
+    pub checksum: u32,
+}
+
+impl ShardHeader {
+    /// Returns `true` if `data` can be the shard described by this header,
+    /// i.e. if its length is `shard_bytes` and `index` is less than
+    /// the shard count of its `kind`.
+    pub fn validate_shard(&self, data: &[u8]) -> bool {
+        let count = match self.kind {
+            ShardKind::Original => self.original_count,
+            ShardKind::Recovery => self.recovery_count,
+        };
+
+        self.index < count && data.len() == self.shard_bytes as usize
+    }
+}
+
+// ======================================================================
+// WireFormatError - PUBLIC
+
+/// Errors that can occur when decoding a [`ShardHeader`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WireFormatError {
+    /// Checksum in header doesn't match checksum of header contents.
+    ChecksumMismatch {
+        /// Checksum calculated from header contents.
+        expected: u32,
+        /// Checksum in header.
+        got: u32,
+    },
+}
+
+// ======================================================================
+// WireFormatError - IMPL DISPLAY
+
+impl fmt::Display for WireFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ChecksumMismatch { expected, got } => {
+                write!(
+                    f,
+                    "checksum mismatch: expected {expected:#010x}, got {got:#010x}"
+                )
+            }
+        }
+    }
+}
+
+// ======================================================================
+// WireFormatError - IMPL ERROR
+
+impl core::error::Error for WireFormatError {}
+
+// ======================================================================
+// FUNCTIONS - PUBLIC
+
+/// Decodes [`ShardHeader`] from `bytes`, verifying its checksum.
+pub fn decode_header(bytes: &[u8; HEADER_BYTES]) -> Result<ShardHeader, WireFormatError> {
+    let word = |i: usize| u32::from_le_bytes([bytes[i], bytes[i + 1], bytes[i + 2], bytes[i + 3]]);
+
+    let expected = crc32c(&bytes[..16]);
+    let got = word(16);
+    if expected != got {
+        return Err(WireFormatError::ChecksumMismatch { expected, got });
+    }
+
+    let index = word(0);
+    let kind = if index & RECOVERY_BIT == 0 {
+        ShardKind::Original
+    } else {
+        ShardKind::Recovery
+    };
+
+    Ok(ShardHeader {
+        kind,
+        index: index & !RECOVERY_BIT,
+        original_count: word(4),
+        recovery_count: word(8),
+        shard_bytes: word(12),
+        checksum: got,
+    })
+}
+
+/// Encodes `header` into bytes, calculating its checksum.
+///
+/// # Panics
+///
+/// If `header.index >= 2^31`.
+pub fn encode_header(header: &ShardHeader) -> [u8; HEADER_BYTES] {
+    assert!(header.index & RECOVERY_BIT == 0);
+
+    let index = match header.kind {
+        ShardKind::Original => header.index,
+        ShardKind::Recovery => header.index | RECOVERY_BIT,
+    };
+
+    let mut bytes = [0; HEADER_BYTES];
+    bytes[0..4].copy_from_slice(&index.to_le_bytes());
+    bytes[4..8].copy_from_slice(&header.original_count.to_le_bytes());
+    bytes[8..12].copy_from_slice(&header.recovery_count.to_le_bytes());
+    bytes[12..16].copy_from_slice(&header.shard_bytes.to_le_bytes());
+
+    let checksum = crc32c(&bytes[..16]);
+    bytes[16..20].copy_from_slice(&checksum.to_le_bytes());
+
+    bytes
+}
+
+// ======================================================================
+// FUNCTIONS - PRIVATE
+
+// Bitwise CRC-32C, headers are short so no lookup table is needed.
+fn crc32c(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 0 {
+                crc >> 1
+            } else {
+                (crc >> 1) ^ CRC32C_POLYNOMIAL
+            };
+        }
+    }
+    !crc
+}
+
+// ======================================================================
+// TESTS
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header(kind: ShardKind, index: u32) -> ShardHeader {
+        ShardHeader {
+            kind,
+            index,
+            original_count: 3,
+            recovery_count: 2,
+            shard_bytes: 64,
+            checksum: 0,
+        }
+    }
+
+    #[test]
+    fn crc32c_check_value() {
+        assert_eq!(crc32c(b"123456789"), 0xE306_9283);
+        assert_eq!(crc32c(b""), 0);
+    }
+
+    #[test]
+    fn roundtrip() {
+        for (kind, index) in [
+            (ShardKind::Original, 0),
+            (ShardKind::Original, 2),
+            (ShardKind::Recovery, 1),
+            (ShardKind::Recovery, RECOVERY_BIT - 1),
+        ] {
+            let header = header(kind, index);
+            let bytes = encode_header(&header);
+            let decoded = decode_header(&bytes).unwrap();
+
+            assert_eq!(decoded.checksum, crc32c(&bytes[..16]));
+            assert_eq!(
+                decoded,
+                ShardHeader {
+                    checksum: decoded.checksum,
+                    ..header
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn checksum_mismatch() {
+        let mut bytes = encode_header(&header(ShardKind::Original, 1));
+        let got = u32::from_le_bytes(bytes[16..20].try_into().unwrap());
+
+        bytes[5] ^= 1;
+        assert_eq!(
+            decode_header(&bytes),
+            Err(WireFormatError::ChecksumMismatch {
+                expected: crc32c(&bytes[..16]),
+                got,
+            })
+        );
+    }
+
+    #[test]
+    fn validate_shard() {
+        assert!(header(ShardKind::Original, 2).validate_shard(&[0; 64]));
+        assert!(header(ShardKind::Recovery, 1).validate_shard(&[0; 64]));
+
+        assert!(!header(ShardKind::Original, 3).validate_shard(&[0; 64]));
+        assert!(!header(ShardKind::Recovery, 2).validate_shard(&[0; 64]));
+        assert!(!header(ShardKind::Original, 0).validate_shard(&[0; 62]));
+    }
+}
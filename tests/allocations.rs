@@ -0,0 +1,95 @@
+// Checks that encoding/decoding rounds after the first one don't allocate.
+//
+// This is its own test binary so that the counting allocator
+// doesn't affect other tests.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+use simd_erasure_core::{ReedSolomonDecoder, ReedSolomonEncoder};
+
+// ======================================================================
+// COUNTING ALLOCATOR
+
+// Allocations are counted per thread so that
+// test harness running in other threads doesn't disturb the count.
+thread_local! {
+    static ALLOCATION_COUNT: Cell<usize> = const { Cell::new(0) };
+}
+
+struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATION_COUNT.with(|count| count.set(count.get() + 1));
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATION_COUNT.with(|count| count.set(count.get() + 1));
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocation_count() -> usize {
+    ALLOCATION_COUNT.with(Cell::get)
+}
+
+// ======================================================================
+// TESTS
+
+#[test]
+fn roundtrips_after_first_dont_allocate() {
+    for (original_count, recovery_count) in [(3, 2), (2, 3), (1000, 100), (100, 1000)] {
+        let shard_bytes = 1024;
+        let original: Vec<Vec<u8>> = (0..original_count)
+            .map(|i| vec![i as u8; shard_bytes])
+            .collect();
+        let recovery =
+            simd_erasure_core::encode(original_count, recovery_count, &original).unwrap();
+
+        let lost_count = original_count.min(recovery_count);
+
+        let mut encoder =
+            ReedSolomonEncoder::new(original_count, recovery_count, shard_bytes).unwrap();
+        let mut decoder =
+            ReedSolomonDecoder::new(original_count, recovery_count, shard_bytes).unwrap();
+
+        let mut roundtrip = || {
+            for shard in &original {
+                encoder.add_original_shard(shard).unwrap();
+            }
+            encoder.encode().unwrap();
+
+            for index in lost_count..original_count {
+                decoder.add_original_shard(index, &original[index]).unwrap();
+            }
+            for index in 0..lost_count {
+                decoder.add_recovery_shard(index, &recovery[index]).unwrap();
+            }
+            let result = decoder.decode().unwrap();
+            assert_eq!(result.restored_original(0), Some(original[0].as_slice()));
+        };
+
+        // First round may e.g. initialize lazily created tables.
+        roundtrip();
+
+        let before = allocation_count();
+        roundtrip();
+        roundtrip();
+        assert_eq!(
+            allocation_count(),
+            before,
+            "{original_count}:{recovery_count}"
+        );
+    }
+}
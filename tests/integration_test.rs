@@ -293,3 +293,34 @@ fn aarch64_neon_random_roundtrips() -> Result<(), Error> {
 
     Ok(())
 }
+
+// ======================================================================
+// TESTS - CRATE ROOT EXPORTS
+
+#[test]
+fn crate_root_exports() -> Result<(), Error> {
+    use simd_erasure_core::{
+        DefaultRateDecoder, DefaultRateEncoder, HighRateDecoder, HighRateEncoder, LowRateDecoder,
+        LowRateEncoder,
+    };
+
+    let original = random_buf(64);
+
+    let mut encoder = DefaultRateEncoder::new(1, 1, 64, NoSimd::new(), None)?;
+    encoder.add_original_shard(&original)?;
+    let recovery = encoder.encode()?.recovery(0).unwrap().to_vec();
+
+    let mut decoder = DefaultRateDecoder::new(1, 1, 64, NoSimd::new(), None)?;
+    decoder.add_recovery_shard(0, &recovery)?;
+    assert_eq!(
+        decoder.decode()?.restored_original(0),
+        Some(original.as_slice())
+    );
+
+    assert!(HighRateEncoder::<NoSimd>::supports(1, 1));
+    assert!(HighRateDecoder::<NoSimd>::supports(1, 1));
+    assert!(LowRateEncoder::<NoSimd>::supports(1, 1));
+    assert!(LowRateDecoder::<NoSimd>::supports(1, 1));
+
+    Ok(())
+}